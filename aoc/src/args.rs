@@ -0,0 +1,211 @@
+use std::env;
+use std::path::PathBuf;
+
+use crate::errors::AOCError;
+use crate::viz::Playback;
+use crate::Part;
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin
+}
+
+/// How results are printed: plain answers, a JSON array, or one JSON
+/// object per line as soon as it is known.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Ndjson
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub parts: Vec<Part>,
+    pub input: Option<InputSource>,
+    pub example: Option<usize>,
+    pub format: Format,
+    /// Set by `--visualize`, `--fps` or `--step` to draw the solving instead of printing answers only.
+    pub visualize: Option<Playback>,
+    pub extra: Vec<String>
+}
+
+pub fn get_args() -> Result<Options, AOCError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    parse_args(&args)
+}
+
+/// Parses `[--part 1|2|both] [--format FORMAT] [--visualize [--fps N] [--step]] [--example N] [INPUT_FILE|-] [ARGS...]`.
+///
+/// `--fps` and `--step` imply `--visualize`.
+/// Without `--example` the first positional argument is the input, any
+/// further ones are passed on to the day as extra arguments.
+pub fn parse_args(args: &[String]) -> Result<Options, AOCError> {
+    let mut parts = vec![Part::One];
+    let mut example: Option<usize> = None;
+    let mut format = Format::Text;
+    let mut visualize: Option<Playback> = None;
+    let mut positional: Vec<String> = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--part needs a value: 1, 2 or both"))?;
+                parts = parse_parts(value)?;
+            },
+            "--part2" => parts = vec![Part::Two],
+            "--format" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--format needs a value: text, json or ndjson"))?;
+                format = parse_format(value)?;
+            },
+            "--visualize" => {
+                visualize.get_or_insert_with(Playback::default);
+            },
+            "--fps" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--fps needs the number of frames per second"))?;
+                visualize.get_or_insert_with(Playback::default).fps = match value.parse::<f64>() {
+                    Ok(fps) if fps > 0.0 && fps.is_finite() => fps,
+                    _ => return Err(AOCError::invalid_argument(format!("--fps expects a positive number, got '{value}'")))
+                };
+            },
+            "--step" => visualize.get_or_insert_with(Playback::default).step = true,
+            "--example" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--example needs the number of the example"))?;
+                example = match value.parse::<usize>() {
+                    Ok(number) if number > 0 => Some(number),
+                    _ => return Err(AOCError::invalid_argument(format!("--example expects a number starting at 1, got '{value}'")))
+                };
+            },
+            "--" => positional.extend(args.by_ref().cloned()),
+            flag if flag.starts_with("--") => {
+                return Err(AOCError::invalid_argument(format!("Unknown option '{flag}'")));
+            },
+            value => positional.push(value.to_owned())
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let input = match example {
+        Some(_) => None,
+        None => match positional.next() {
+            Some(path) if path == "-" => Some(InputSource::Stdin),
+            Some(path) => Some(InputSource::File(PathBuf::from(path))),
+            None => return Err(AOCError::invalid_argument("Missing INPUT_FILE, use - to read from stdin"))
+        }
+    };
+
+    if input == Some(InputSource::Stdin) && visualize.is_some_and(|playback| playback.step) {
+        return Err(AOCError::invalid_argument("--step reads keys from stdin, so the input can't come from stdin too"));
+    }
+
+    Ok(Options {
+        parts,
+        input,
+        example,
+        format,
+        visualize,
+        extra: positional.collect()
+    })
+}
+
+pub fn parse_parts(value: &str) -> Result<Vec<Part>, AOCError> {
+    match value {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        "both" => Ok(vec![Part::One, Part::Two]),
+        _ => Err(AOCError::invalid_argument(format!("'{value}' is not a valid part, expected 1, 2 or both")))
+    }
+}
+
+pub fn parse_format(value: &str) -> Result<Format, AOCError> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "ndjson" => Ok(Format::Ndjson),
+        _ => Err(AOCError::invalid_argument(format!("'{value}' is not a valid format, expected text, json or ndjson")))
+    }
+}
+
+pub fn usage(program_name: &str, extra: &str) -> String {
+    let usage = format!("Usage: {program_name} [--part 1|2|both] [--format text|json|ndjson] [--visualize [--fps N] [--step]] [--example N | INPUT_FILE | -]");
+    if extra.is_empty() {
+        usage
+    } else {
+        format!("{usage} {extra}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args("input.txt")).unwrap();
+        assert_eq!(options.parts, vec![Part::One]);
+        assert_eq!(options.format, Format::Text);
+        assert_eq!(options.input, Some(InputSource::File(PathBuf::from("input.txt"))));
+        assert!(options.extra.is_empty());
+        assert_eq!(options.visualize, None);
+    }
+
+    #[test]
+    fn test_parse_args_visualize() {
+        let visualize = |line: &str| parse_args(&args(line)).unwrap().visualize;
+        assert_eq!(visualize("--visualize input.txt"), Some(Playback::default()));
+        assert_eq!(visualize("--fps 2.5 input.txt"), Some(Playback { fps: 2.5, step: false }));
+        assert_eq!(visualize("--step --visualize --example 1"), Some(Playback { step: true, ..Playback::default() }));
+        assert!(parse_args(&args("--fps 0 input.txt")).unwrap_err().to_string().contains("'0'"));
+        assert!(parse_args(&args("--step -")).unwrap_err().to_string().contains("stdin"));
+        assert_eq!(visualize("--visualize -"), Some(Playback::default()));
+    }
+
+    #[test]
+    fn test_parse_args_part() {
+        assert_eq!(parse_args(&args("--part 2 input.txt")).unwrap().parts, vec![Part::Two]);
+        assert_eq!(parse_args(&args("input.txt --part both")).unwrap().parts, vec![Part::One, Part::Two]);
+        assert_eq!(parse_args(&args("--part2 input.txt")).unwrap().parts, vec![Part::Two]);
+    }
+
+    #[test]
+    fn test_parse_args_format() {
+        assert_eq!(parse_args(&args("--format json input.txt")).unwrap().format, Format::Json);
+        assert_eq!(parse_args(&args("- --format ndjson")).unwrap().format, Format::Ndjson);
+        assert!(parse_args(&args("--format xml input.txt")).unwrap_err().to_string().contains("'xml'"));
+    }
+
+    #[test]
+    fn test_parse_args_stdin_and_extra() {
+        let options = parse_args(&args("- 12 13 14")).unwrap();
+        assert_eq!(options.input, Some(InputSource::Stdin));
+        assert_eq!(options.extra, vec!["12", "13", "14"]);
+    }
+
+    #[test]
+    fn test_parse_args_example() {
+        let options = parse_args(&args("--example 2 100")).unwrap();
+        assert_eq!(options.example, Some(2));
+        assert_eq!(options.input, None);
+        assert_eq!(options.extra, vec!["100"]);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        let message = |line: &str| parse_args(&args(line)).unwrap_err().to_string();
+        assert!(message("").contains("INPUT_FILE"));
+        assert!(message("--part 3 input.txt").contains("'3'"));
+        assert!(message("--verbose input.txt").contains("'--verbose'"));
+        assert!(message("--example zero").contains("'zero'"));
+        assert!(message("input.txt --part").contains("--part"));
+    }
+}
@@ -1,24 +1,259 @@
+use std::any::Any;
+use std::error::Error;
 use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug)]
-pub struct AOCError {
-    message: String,
+pub enum AOCError {
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error
+    },
+    Parse(ParseError),
+    InvalidArgument(String),
+    NoSolution(String),
+    /// The solution panicked, with the panic message.
+    Panicked(String),
+    /// The solution was given up on after running this long.
+    TimedOut(Duration),
+    /// The solution never got to run, every thread was held by one that
+    /// timed out.
+    NotStarted
+}
+
+/// Where and why the input could not be parsed. Line and column start at 1.
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+    pub source: Option<Box<dyn Error + Send + Sync>>
 }
 
 impl AOCError {
-    pub fn new(message: String) -> Self {
-        Self { message }
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::Parse(ParseError {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            text: None,
+            source: None
+        })
+    }
+
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::InvalidArgument(message.into())
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Self::NoSolution(message.into())
+    }
+
+    /// From the payload of a caught panic.
+    pub fn panicked(payload: &(dyn Any + Send)) -> Self {
+        let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("unknown panic"));
+        Self::Panicked(message)
+    }
+
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        Self::Io { path: Some(path.to_path_buf()), source }
+    }
+
+    /// A stable name of the variant, for machine readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "io",
+            Self::Parse(_) => "parse",
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::NoSolution(_) => "no_solution",
+            Self::Panicked(_) => "panic",
+            Self::TimedOut(_) => "timeout",
+            Self::NotStarted => "not_started"
+        }
+    }
+
+    /// Records the (1-based) line number and its text on a parse error.
+    /// Keeps a line that was already set by a more specific parser.
+    pub fn at_line(mut self, number: usize, text: &str) -> Self {
+        if let Self::Parse(error) = &mut self {
+            error.line.get_or_insert(number);
+            error.text.get_or_insert_with(|| text.to_owned());
+        }
+        self
+    }
+
+    /// Records the (1-based) column on a parse error.
+    pub fn at_column(mut self, column: usize) -> Self {
+        if let Self::Parse(error) = &mut self {
+            error.column.get_or_insert(column);
+        }
+        self
+    }
+
+    /// Points the column at `token`, which has to be a slice of the line `text`.
+    pub fn at_column_of(mut self, text: &str, token: &str) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+        if offset > text.len() || !text.is_char_boundary(offset) {
+            return self;
+        }
+        if let Self::Parse(error) = &mut self {
+            error.text.get_or_insert_with(|| text.to_owned());
+        }
+        self.at_column(text[..offset].chars().count() + 1)
+    }
+
+    /// Records the file the failing input was read from.
+    pub fn in_file(mut self, path: &Path) -> Self {
+        match &mut self {
+            Self::Parse(error) => {
+                error.file.get_or_insert_with(|| path.to_path_buf());
+            },
+            Self::Io { path: io_path, .. } => {
+                io_path.get_or_insert_with(|| path.to_path_buf());
+            },
+            _ => ()
+        }
+        self
+    }
+
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        if let Self::Parse(error) = &mut self {
+            error.source = Some(Box::new(source));
+        }
+        self
     }
 }
 
-impl From<&str> for AOCError {
-    fn from(message: &str) -> Self {
-        Self::new(String::from(message))
+impl From<io::Error> for AOCError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { path: None, source }
+    }
+}
+
+impl From<ParseIntError> for AOCError {
+    fn from(source: ParseIntError) -> Self {
+        Self::parse(format!("Invalid number: {source}")).with_source(source)
     }
 }
 
 impl fmt::Display for AOCError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.message)
+        match self {
+            Self::Io { path: Some(path), source } => write!(f, "Could not read {}: {source}", path.display()),
+            Self::Io { path: None, source } => write!(f, "IO error: {source}"),
+            Self::Parse(error) => write!(f, "{error}"),
+            Self::InvalidArgument(message) => write!(f, "{message}"),
+            Self::NoSolution(message) => write!(f, "No solution found: {message}"),
+            Self::Panicked(message) => write!(f, "Panicked: {message}"),
+            Self::TimedOut(duration) => write!(f, "Timed out after {duration:?}"),
+            Self::NotStarted => write!(f, "Not started, every thread is still busy with a task that timed out")
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        let Some(line) = self.line else {
+            if let Some(file) = &self.file {
+                write!(f, "\n --> {}", file.display())?;
+            }
+            return Ok(());
+        };
+
+        let file = self.file.as_ref()
+            .map(|file| file.display().to_string())
+            .unwrap_or(String::from("<input>"));
+        match self.column {
+            Some(column) => write!(f, "\n --> {file}:{line}:{column}")?,
+            None => write!(f, "\n --> {file}:{line}")?
+        }
+
+        if let Some(text) = &self.text {
+            let gutter = " ".repeat(line.to_string().len());
+            write!(f, "\n{gutter} |\n{line} | {text}")?;
+            if let Some(column) = self.column {
+                write!(f, "\n{gutter} | {}^", " ".repeat(column.saturating_sub(1)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for AOCError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse(error) => error.source.as_ref().map(|source| source.as_ref() as &(dyn Error + 'static)),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_with_caret() {
+        let error = AOCError::parse("'4x' is not a number.")
+            .at_line(3, "Game 4x: 1 red")
+            .at_column(6)
+            .in_file(Path::new("tests/input.txt"));
+        assert_eq!(error.to_string(), "'4x' is not a number.\n \
+            --> tests/input.txt:3:6\n  \
+            |\n\
+            3 | Game 4x: 1 red\n  \
+            |      ^");
+    }
+
+    #[test]
+    fn test_render_without_location() {
+        assert_eq!(AOCError::parse("Empty input.").to_string(), "Empty input.");
+        assert_eq!(AOCError::no_solution("never reached ZZZ").to_string(), "No solution found: never reached ZZZ");
+    }
+
+    #[test]
+    fn test_at_column_of() {
+        let line = "Card 1: 41 4x | 83";
+        let error = AOCError::parse("bad").at_column_of(line, &line[11..13]).at_line(1, line);
+        match error {
+            AOCError::Parse(error) => {
+                assert_eq!(error.column, Some(12));
+                assert_eq!(error.text.as_deref(), Some(line));
+            },
+            _ => panic!("expected a parse error")
+        }
+
+        let error = AOCError::parse("bad").at_column_of(line, &String::from("41"));
+        assert!(matches!(error, AOCError::Parse(ParseError { column: None, .. })));
+    }
+
+    #[test]
+    fn test_innermost_location_wins() {
+        let error = AOCError::parse("bad").at_line(2, "two").at_line(1, "one");
+        match error {
+            AOCError::Parse(error) => assert_eq!(error.line, Some(2)),
+            _ => panic!("expected a parse error")
+        }
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let error = AOCError::from("x".parse::<u32>().unwrap_err());
+        assert!(error.source().is_some());
+        assert!(error.to_string().starts_with("Invalid number"));
+
+        let error = AOCError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert_eq!(error.source().unwrap().to_string(), "missing");
+        assert!(AOCError::invalid_argument("--part").source().is_none());
     }
 }
@@ -0,0 +1,277 @@
+//! Runs the examples in a day's `tests` directory against the answers
+//! written next to them. An example `tests/input.txt` is checked when there
+//! is a `tests/input.toml` like
+//!
+//! ```toml
+//! part1 = 374
+//! part2 = 8410
+//! # Passed to Solution::with_args
+//! args = "100"
+//! ```
+//!
+//! Either part can be left out. A day opts in with an integration test
+//! declared with `harness = false` in its `Cargo.toml` and containing only
+//! `aoc::example_tests!(day11::Day11);`. Every example becomes its own test.
+
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::errors::AOCError;
+use crate::toml;
+use crate::{Part, Solution};
+
+/// An example input with the answers expected for it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Example {
+    /// The file name without `.txt`.
+    pub name: String,
+    pub path: PathBuf,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub args: Vec<String>
+}
+
+impl Example {
+    /// Reads the answers for the example at `path`, `None` if it has no sidecar.
+    pub fn load(path: &Path) -> Result<Option<Self>, AOCError> {
+        let sidecar = path.with_extension("toml");
+        let contents = match fs::read_to_string(&sidecar) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(AOCError::io(&sidecar, error))
+        };
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let mut example = Self { name, path: path.to_path_buf(), ..Self::default() };
+
+        for section in toml::parse(&contents).map_err(|error| error.in_file(&sidecar))? {
+            if let Some(header) = section.header {
+                return Err(header.error("Expected only answers, no sections.").in_file(&sidecar));
+            }
+            for entry in section.entries {
+                match entry.key.name {
+                    "part1" => example.part1 = Some(entry.value),
+                    "part2" => example.part2 = Some(entry.value),
+                    "args" => example.args = entry.value.split_whitespace().map(String::from).collect(),
+                    key => return Err(entry.key.error(format!("Unknown key '{key}', expected part1, part2 or args.")).in_file(&sidecar))
+                }
+            }
+        }
+        Ok(Some(example))
+    }
+
+    /// Solves the parts with an expected answer, describing every mismatch.
+    pub fn check<S: Solution>(&self) -> Result<(), String> {
+        let solution = S::with_args(&self.args).map_err(|error| error.to_string())?;
+        let input = solution.parse_input(self.path.as_path()).map_err(|error| error.to_string())?;
+
+        let mut failures = Vec::new();
+        for (part, expected) in [(Part::One, &self.part1), (Part::Two, &self.part2)] {
+            let Some(expected) = expected else {
+                continue;
+            };
+            let answer = match part {
+                Part::One => solution.part1(&input).map(|answer| answer.to_string()),
+                Part::Two => solution.part2(&input).map(|answer| answer.to_string())
+            };
+            let number = part.number();
+            match answer {
+                Ok(answer) if answer == *expected => (),
+                Ok(answer) => failures.push(format!("part {number}: expected {expected}, got {answer}")),
+                Err(error) => failures.push(format!("part {number}: expected {expected}, failed with {error}"))
+            }
+        }
+        if failures.is_empty() {Ok(())} else {Err(failures.join("\n"))}
+    }
+}
+
+/// The examples in `directory` which have answers, sorted by name.
+pub fn find_examples(directory: &Path) -> Result<Vec<Example>, AOCError> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(directory)
+        .map_err(|error| AOCError::io(directory, error))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    paths.sort();
+
+    let mut examples = Vec::new();
+    for path in paths {
+        examples.extend(Example::load(&path)?);
+    }
+    Ok(examples)
+}
+
+/// `main` of a day's example tests. Understands the arguments `cargo test`
+/// passes on: name filters, `--exact`, `--skip` and `--list`.
+pub fn run_examples<S: Solution>(manifest_dir: &Path) -> ExitCode {
+    let mut filters: Vec<String> = Vec::new();
+    let mut skipped: Vec<String> = Vec::new();
+    let (mut exact, mut list) = (false, false);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--exact" => exact = true,
+            "--list" => list = true,
+            "--skip" => skipped.extend(args.next()),
+            // Options with a value this harness has no use for.
+            "--test-threads" | "--format" | "--color" | "--logfile" | "-Z" => {
+                args.next();
+            },
+            flag if flag.starts_with('-') => (),
+            filter => filters.push(filter.to_owned())
+        }
+    }
+    let matches = |name: &str, filter: &String| if exact {name == filter} else {name.contains(filter.as_str())};
+
+    let examples = match find_examples(&manifest_dir.join("tests")) {
+        Ok(examples) => examples,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let selected: Vec<&Example> = examples.iter()
+        .filter(|example| filters.is_empty() || filters.iter().any(|filter| matches(&example.name, filter)))
+        .filter(|example| !skipped.iter().any(|filter| matches(&example.name, filter)))
+        .collect();
+
+    if list {
+        for example in &selected {
+            println!("{}: test", example.name);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    println!();
+    println!("running {} tests", selected.len());
+    let mut failures: Vec<(&str, String)> = Vec::new();
+    for example in &selected {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| example.check::<S>()))
+            .unwrap_or_else(|payload| Err(AOCError::panicked(payload.as_ref()).to_string()));
+        match result {
+            Ok(()) => println!("test {} ... ok", example.name),
+            Err(message) => {
+                println!("test {} ... FAILED", example.name);
+                failures.push((&example.name, message));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!();
+        println!("failures:");
+        for (name, message) in &failures {
+            println!();
+            println!("---- {name} ----");
+            println!("{message}");
+        }
+    }
+    println!();
+    let status = if failures.is_empty() {"ok"} else {"FAILED"};
+    println!(
+        "test result: {status}. {} passed; {} failed; 0 ignored; 0 measured; {} filtered out",
+        selected.len() - failures.len(), failures.len(), examples.len() - selected.len()
+    );
+    println!();
+    if failures.is_empty() {ExitCode::SUCCESS} else {ExitCode::FAILURE}
+}
+
+/// Expands to the `main` of a day's example tests, see [`run_examples`].
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        fn main() -> std::process::ExitCode {
+            $crate::examples::run_examples::<$solution>(std::path::Path::new(env!("CARGO_MANIFEST_DIR")))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// Adds up the numbers of the input, scaled by its only argument.
+    #[derive(Default)]
+    struct Sum {
+        factor: u64
+    }
+
+    impl Solution for Sum {
+        const DAY: u32 = 0;
+
+        type Input = Vec<u64>;
+        type Answer1 = u64;
+        type Answer2 = u64;
+
+        fn with_args(args: &[String]) -> Result<Self, AOCError> {
+            let factor = args.first().map_or(Ok(1), |factor| factor.parse())?;
+            Ok(Self { factor })
+        }
+
+        fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+            input.split_whitespace().map(|number| Ok(number.parse()?)).collect()
+        }
+
+        fn part1(&self, numbers: &Self::Input) -> Result<u64, AOCError> {
+            Ok(numbers.iter().sum::<u64>() * self.factor)
+        }
+
+        fn part2(&self, numbers: &Self::Input) -> Result<u64, AOCError> {
+            numbers.iter().max().copied().ok_or(AOCError::no_solution("no numbers"))
+        }
+    }
+
+    fn directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let directory = env::temp_dir().join(format!("aoc-examples-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        for (file, contents) in files {
+            fs::write(directory.join(file), contents).unwrap();
+        }
+        directory
+    }
+
+    #[test]
+    fn test_find_examples() {
+        let directory = directory("find", &[
+            ("b.txt", "1 2"), ("b.toml", "# sums\npart1 = 6\nargs = \"2\"\n"),
+            ("a.txt", "3"), ("a.toml", "part2 = \"3\" # max"),
+            ("c.txt", "without answers")
+        ]);
+        let examples = find_examples(&directory).unwrap();
+        assert_eq!(examples.iter().map(|example| example.name.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(examples[0].part2.as_deref(), Some("3"));
+        assert_eq!(examples[1].args, vec!["2"]);
+        assert!(examples.iter().all(|example| example.check::<Sum>().is_ok()));
+
+        fs::write(directory.join("c.toml"), "part3 = 1").unwrap();
+        let error = find_examples(&directory).unwrap_err().to_string();
+        assert!(error.contains("Unknown key 'part3'"));
+        assert!(error.contains("c.toml:1"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_check_reports_every_part() {
+        let directory = directory("check", &[("x.txt", "1 2"), ("empty.txt", "")]);
+        let example = |path: &str, part1: &str, part2: &str| Example {
+            name: String::from(path),
+            path: directory.join(path),
+            part1: Some(part1.to_owned()),
+            part2: Some(part2.to_owned()),
+            args: Vec::new()
+        };
+        assert_eq!(
+            example("x.txt", "4", "3").check::<Sum>().unwrap_err(),
+            "part 1: expected 4, got 3\npart 2: expected 3, got 2"
+        );
+        assert!(example("empty.txt", "0", "0").check::<Sum>().unwrap_err().contains("failed with No solution found: no numbers"));
+        let with_args = Example { args: vec![String::from("x")], ..example("x.txt", "3", "2") };
+        assert!(with_args.check::<Sum>().unwrap_err().contains("Invalid number"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
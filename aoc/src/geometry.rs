@@ -0,0 +1,360 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::math::gcd;
+
+/// The integer types points and vectors can be built from.
+pub trait Number: Copy + Ord + fmt::Debug
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The absolute difference, which fits into `Self` for unsigned types too.
+    fn distance(self, other: Self) -> Self;
+
+    fn to_i64(self) -> i64;
+}
+
+macro_rules! number {
+    ($($number:ty),*) => {
+        $(impl Number for $number {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn distance(self, other: Self) -> Self {
+                if self > other {self - other} else {other - self}
+            }
+
+            fn to_i64(self) -> i64 {
+                self as i64
+            }
+        })*
+    };
+}
+
+number!(i32, i64, isize, u32, u64, usize);
+
+/// A position on the plane. `y` grows downwards, like the rows of the input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T
+}
+
+/// A displacement between two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T
+}
+
+pub type UPoint = Point<usize>;
+pub type IPoint = Point<i64>;
+pub type UVector = Vector<usize>;
+pub type IVector = Vector<i64>;
+
+impl<T: Number> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// Number of king moves between the points.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    pub fn euclidean(self, other: Self) -> f64 {
+        let dx = self.x.distance(other.x).to_i64() as f64;
+        let dy = self.y.distance(other.y).to_i64() as f64;
+        dx.hypot(dy)
+    }
+}
+
+impl Point<usize> {
+    /// The neighbouring point in `direction`, unless it would leave the first quadrant.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let Vector { x, y } = direction.vector();
+        Some(Self::new(self.x.checked_add_signed(x)?, self.y.checked_add_signed(y)?))
+    }
+}
+
+impl Point<i64> {
+    pub fn step(self, direction: Direction) -> Self {
+        let Vector { x, y } = direction.vector();
+        Self::new(self.x + x as i64, self.y + y as i64)
+    }
+}
+
+impl<T: Number> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Number> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, vector: Vector<T>) -> Self {
+        Self::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl<T: Number> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, vector: Vector<T>) {
+        *self = *self + vector;
+    }
+}
+
+impl<T: Number> Sub<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, vector: Vector<T>) -> Self {
+        Self::new(self.x - vector.x, self.y - vector.y)
+    }
+}
+
+impl<T: Number> SubAssign<Vector<T>> for Point<T> {
+    fn sub_assign(&mut self, vector: Vector<T>) {
+        *self = *self - vector;
+    }
+}
+
+impl<T: Number> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, other: Self) -> Vector<T> {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Number> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Number> Sub for Vector<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Number> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Number + Neg<Output = T>> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// Compass directions, with north pointing up to the first line of the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+    pub const ALL: [Direction; 8] = [
+        Direction::North, Direction::NorthEast, Direction::East, Direction::SouthEast,
+        Direction::South, Direction::SouthWest, Direction::West, Direction::NorthWest
+    ];
+
+    pub fn vector(self) -> Vector<isize> {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::NorthEast => Vector::new(1, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::SouthEast => Vector::new(1, 1),
+            Direction::South => Vector::new(0, 1),
+            Direction::SouthWest => Vector::new(-1, 1),
+            Direction::West => Vector::new(-1, 0),
+            Direction::NorthWest => Vector::new(-1, -1)
+        }
+    }
+
+    /// Turns clockwise by `eighths` eighths of a full turn.
+    pub fn turn(self, eighths: i32) -> Self {
+        let index = Self::ALL.iter().position(|direction| *direction == self).unwrap_or(0);
+        Self::ALL[(index as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn(-2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::CARDINAL.contains(&self)
+    }
+}
+
+/// The smallest axis aligned rectangle containing some points, both corners included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>
+}
+
+impl<T: Number> BoundingBox<T> {
+    /// `None` if there are no points.
+    pub fn of(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self { min: first, max: first }, |bounds, point| Self {
+            min: Point::new(bounds.min.x.min(point.x), bounds.min.y.min(point.y)),
+            max: Point::new(bounds.max.x.max(point.x), bounds.max.y.max(point.y))
+        }))
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+/// Twice the area enclosed by the polygon through `vertices`, using the
+/// shoelace formula. Doubled so it stays an integer.
+pub fn double_area<T: Number>(vertices: &[Point<T>]) -> u64 {
+    let sum: i64 = vertices.iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.x.to_i64() * b.y.to_i64() - b.x.to_i64() * a.y.to_i64())
+        .sum();
+    sum.unsigned_abs()
+}
+
+pub fn area<T: Number>(vertices: &[Point<T>]) -> f64 {
+    double_area(vertices) as f64 / 2.0
+}
+
+/// Number of lattice points on the edges of the polygon through `vertices`.
+pub fn boundary_points<T: Number>(vertices: &[Point<T>]) -> u64 {
+    vertices.iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| gcd(a.x.distance(b.x).to_i64() as u64, a.y.distance(b.y).to_i64() as u64))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon through `vertices`,
+/// following Pick's theorem: `area = interior + boundary / 2 - 1`.
+pub fn interior_points<T: Number>(vertices: &[Point<T>]) -> u64 {
+    (double_area(vertices) + 2).saturating_sub(boundary_points(vertices)) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1usize, 5);
+        let b = Point::new(4usize, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(IPoint::new(-2, 3).manhattan(IPoint::new(2, -3)), 10);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let point = IPoint::new(1, 2) + IVector::new(3, -4) * 2;
+        assert_eq!(point, IPoint::new(7, -6));
+        assert_eq!(point - IPoint::new(1, 2), IVector::new(6, -8));
+        assert_eq!(-IVector::new(1, -1), IVector::new(-1, 1));
+        assert_eq!(UPoint::new(0, 3).step(Direction::West), None);
+        assert_eq!(UPoint::new(0, 3).step(Direction::NorthEast), Some(UPoint::new(1, 2)));
+        assert_eq!(IPoint::new(0, 0).step(Direction::SouthWest), IPoint::new(-1, 1));
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthWest.turn(1), Direction::North);
+        assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+        assert!(Direction::SouthWest.is_diagonal());
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().vector(), -direction.vector());
+        }
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let bounds = BoundingBox::of([IPoint::new(2, -1), IPoint::new(-3, 4), IPoint::new(0, 0)]).unwrap();
+        assert_eq!(bounds.min, IPoint::new(-3, -1));
+        assert_eq!(bounds.max, IPoint::new(2, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert!(bounds.contains(IPoint::new(0, 4)));
+        assert!(!bounds.contains(IPoint::new(3, 0)));
+        assert_eq!(BoundingBox::<i64>::of([]), None);
+    }
+
+    #[test]
+    fn test_polygon() {
+        let square = [UPoint::new(0, 0), UPoint::new(4, 0), UPoint::new(4, 4), UPoint::new(0, 4)];
+        assert_eq!(area(&square), 16.0);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        let triangle = [IPoint::new(0, 0), IPoint::new(3, 1), IPoint::new(1, 3)];
+        assert_eq!(double_area(&triangle), 8);
+        assert_eq!(boundary_points(&triangle), 4);
+        assert_eq!(interior_points(&triangle), 3);
+    }
+}
@@ -0,0 +1,358 @@
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::hash::Hash;
+
+/// Index of an interned node, stable for the lifetime of its graph.
+pub type NodeId = usize;
+
+/// A directed graph whose nodes are interned into dense [`NodeId`]s, with
+/// outgoing edges kept in insertion order.
+#[derive(Clone, Debug)]
+pub struct Graph<N, E = ()> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, E)>>
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Self { nodes: Vec::new(), ids: HashMap::new(), edges: Vec::new() }
+    }
+
+    /// The id of `node`, adding it first if it isn't in the graph yet.
+    pub fn intern(&mut self, node: N) -> NodeId {
+        if let Some(id) = self.ids.get(&node) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    /// The id of `node`, which can also be borrowed like a `&str` for `String` nodes.
+    pub fn id<Q>(&self, node: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized
+    {
+        self.ids.get(node).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, edge: E) {
+        self.edges[from].push((to, edge));
+    }
+
+    /// Outgoing edges of `id` with the nodes they lead to.
+    pub fn edges(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> {
+        self.edges[id].iter().map(|(to, edge)| (*to, edge))
+    }
+
+    pub fn successors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|(to, _)| *to)
+    }
+
+    /// Number of edges needed to reach every node from `start`, `None` where unreachable.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            let distance = distances[id].unwrap_or(0) + 1;
+            for next in self.successors(id) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Nodes reachable from `start` in depth first preorder.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            order.push(id);
+            let successors: Vec<NodeId> = self.successors(id).collect();
+            stack.extend(successors.into_iter().rev().filter(|next| !visited[*next]));
+        }
+        order
+    }
+
+    /// Cost of the cheapest path from `start` to every node, `None` where unreachable.
+    pub fn dijkstra<W>(&self, start: NodeId, weight: W) -> Vec<Option<u64>>
+    where
+        W: Fn(&E) -> u64
+    {
+        let mut costs = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((cost, id))) = queue.pop() {
+            if costs[id].is_some() {
+                continue;
+            }
+            costs[id] = Some(cost);
+            for (next, edge) in self.edges(id) {
+                if costs[next].is_none() {
+                    queue.push(Reverse((cost + weight(edge), next)));
+                }
+            }
+        }
+        costs
+    }
+
+    /// The cheapest path from `start` to `goal` and its cost, found with A*.
+    /// `heuristic` must never overestimate the remaining cost to `goal`,
+    /// `|_| 0` turns this into Dijkstra's algorithm.
+    pub fn shortest_path<W, H>(&self, start: NodeId, goal: NodeId, weight: W, heuristic: H) -> Option<(u64, Vec<NodeId>)>
+    where
+        W: Fn(&E) -> u64,
+        H: Fn(NodeId) -> u64
+    {
+        let mut costs: Vec<Option<u64>> = vec![None; self.len()];
+        let mut previous: Vec<Option<NodeId>> = vec![None; self.len()];
+        let mut done = vec![false; self.len()];
+        costs[start] = Some(0);
+        let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+        while let Some(Reverse((_, cost, id))) = queue.pop() {
+            if done[id] {
+                continue;
+            }
+            done[id] = true;
+            if id == goal {
+                let mut path = vec![goal];
+                while let Some(before) = previous[path[path.len() - 1]] {
+                    path.push(before);
+                }
+                path.reverse();
+                return Some((cost, path));
+            }
+            for (next, edge) in self.edges(id) {
+                let next_cost = cost + weight(edge);
+                if !done[next] && costs[next].is_none_or(|known| next_cost < known) {
+                    costs[next] = Some(next_cost);
+                    previous[next] = Some(id);
+                    queue.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Groups of nodes connected when ignoring edge directions, each sorted
+    /// by id and ordered by their smallest id.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut neighbours: Vec<Vec<NodeId>> = vec![Vec::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, _) in edges {
+                neighbours[from].push(*to);
+                neighbours[*to].push(from);
+            }
+        }
+
+        let mut component: Vec<Option<usize>> = vec![None; self.len()];
+        let mut components: Vec<Vec<NodeId>> = Vec::new();
+        for start in 0..self.len() {
+            if component[start].is_some() {
+                continue;
+            }
+            let mut members = vec![start];
+            component[start] = Some(components.len());
+            let mut stack = vec![start];
+            while let Some(id) = stack.pop() {
+                for next in &neighbours[id] {
+                    if component[*next].is_none() {
+                        component[*next] = Some(components.len());
+                        members.push(*next);
+                        stack.push(*next);
+                    }
+                }
+            }
+            members.sort();
+            components.push(members);
+        }
+        components
+    }
+
+    /// Whether the graph has no directed cycle, self loops included.
+    pub fn is_acyclic(&self) -> bool {
+        let mut incoming = vec![0; self.len()];
+        for to in self.edges.iter().flatten().map(|(to, _)| *to) {
+            incoming[to] += 1;
+        }
+        let mut ready: Vec<NodeId> = (0..self.len()).filter(|id| incoming[*id] == 0).collect();
+        let mut removed = 0;
+        while let Some(id) = ready.pop() {
+            removed += 1;
+            for next in self.successors(id) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+        removed == self.len()
+    }
+
+    /// Renders the graph in Graphviz DOT, filling the `highlighted` nodes.
+    /// Edges are labelled with whatever `label` returns for them.
+    pub fn to_dot<L>(&self, highlighted: &[NodeId], label: L) -> String
+    where
+        N: fmt::Display,
+        L: Fn(&E) -> Option<String>
+    {
+        let mut dot = String::from("digraph {\n");
+        for (id, node) in self.nodes() {
+            let style = if highlighted.contains(&id) {", style=filled, fillcolor=gold"} else {""};
+            let _ = writeln!(dot, "    {id} [label=\"{}\"{style}];", escape(&node.to_string()));
+        }
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, edge) in edges {
+                match label(edge) {
+                    Some(label) => {
+                        let _ = writeln!(dot, "    {from} -> {to} [label=\"{}\"];", escape(&label));
+                    },
+                    None => {
+                        let _ = writeln!(dot, "    {from} -> {to};");
+                    }
+                }
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+impl<N: Clone + Eq + Hash, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Where a sequence of states starts repeating: the states at `offset` and
+/// `offset + length` are the first pair that are equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub offset: usize,
+    pub length: usize
+}
+
+/// Follows `next` from `start` until a state comes up a second time.
+/// Only terminates if the states eventually repeat.
+pub fn find_cycle<S, F>(start: S, mut next: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = start;
+    for step in 0.. {
+        if let Some(offset) = seen.insert(state.clone(), step) {
+            return Cycle { offset, length: step - offset };
+        }
+        state = next(&state);
+    }
+    unreachable!("the step counter overflowed before a state repeated")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -1-> b -2-> c -1-> d, a -5-> d and e on its own.
+    fn weighted() -> Graph<&'static str, u64> {
+        let mut graph = Graph::new();
+        let [a, b, c, d, _] = ["a", "b", "c", "d", "e"].map(|node| graph.intern(node));
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 2);
+        graph.add_edge(c, d, 1);
+        graph.add_edge(a, d, 5);
+        graph
+    }
+
+    #[test]
+    fn test_intern() {
+        let mut graph: Graph<String> = Graph::new();
+        let a = graph.intern(String::from("AAA"));
+        let b = graph.intern(String::from("BBB"));
+        assert_eq!(graph.intern(String::from("AAA")), a);
+        assert_eq!(graph.id(&String::from("BBB")), Some(b));
+        assert_eq!(graph.id("BBB"), Some(b));
+        assert_eq!(graph.node(b), "BBB");
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn test_traversals() {
+        let graph = weighted();
+        assert_eq!(graph.bfs(0), vec![Some(0), Some(1), Some(2), Some(1), None]);
+        assert_eq!(graph.dfs(0), vec![0, 1, 2, 3]);
+        assert_eq!(graph.dfs(4), vec![4]);
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let graph = weighted();
+        assert_eq!(graph.dijkstra(0, |weight| *weight), vec![Some(0), Some(1), Some(3), Some(4), None]);
+        assert_eq!(graph.shortest_path(0, 3, |weight| *weight, |_| 0), Some((4, vec![0, 1, 2, 3])));
+        assert_eq!(graph.shortest_path(0, 3, |_| 1, |id| u64::from(id != 3)), Some((1, vec![0, 3])));
+        assert_eq!(graph.shortest_path(0, 4, |weight| *weight, |_| 0), None);
+    }
+
+    #[test]
+    fn test_components_and_cycles() {
+        let mut graph = weighted();
+        assert_eq!(graph.connected_components(), vec![vec![0, 1, 2, 3], vec![4]]);
+        assert!(graph.is_acyclic());
+        graph.add_edge(3, 1, 0);
+        assert!(!graph.is_acyclic());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        assert_eq!(find_cycle(0, |n| (n + 1) % 5), Cycle { offset: 0, length: 5 });
+        assert_eq!(find_cycle(20, |n| if *n > 3 {n - 4} else {n + 1}), Cycle { offset: 4, length: 5 });
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = weighted().to_dot(&[1], |weight| Some(weight.to_string()));
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    1 [label=\"b\", style=filled, fillcolor=gold];"));
+        assert!(dot.contains("    0 -> 3 [label=\"5\"];"));
+
+        let mut graph: Graph<&str> = Graph::new();
+        let quote = graph.intern("\"");
+        graph.add_edge(quote, quote, ());
+        assert_eq!(graph.to_dot(&[], |_| None), "digraph {\n    0 [label=\"\\\"\"];\n    0 -> 0;\n}");
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::errors::AOCError;
+
+/// Column and row of a cell, both starting at 0 in the top left corner.
+/// Anything converting into it, like a [`crate::geometry::UPoint`], can index the grid.
+pub type Position = (usize, usize);
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1)
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone
+    {
+        Self { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Builds a grid from rows which all have to be as long as the first one.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, AOCError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AOCError::parse(format!("Expected {width} cells like the first row, found {} in row {}.", row.len(), y + 1)));
+            }
+            cells.extend(row);
+        }
+        Ok(Self { width, height, cells })
+    }
+
+    /// Parses one row per line, converting every character with `cell`.
+    /// Errors point at the line and column of the offending character.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, AOCError>
+    where
+        F: FnMut(char) -> Result<T, AOCError>
+    {
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut length = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c).map_err(|error| error.at_column(x + 1).at_line(y + 1, line))?;
+                cells.push(value);
+                length += 1;
+            }
+            let width = *width.get_or_insert(length);
+            if length != width {
+                return Err(AOCError::parse(format!("Expected {width} characters like the first line, found {length}."))
+                    .at_line(y + 1, line));
+            }
+            height += 1;
+        }
+        Ok(Self { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: impl Into<Position>) -> bool {
+        let (x, y) = position.into();
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: impl Into<Position>) -> Option<&T> {
+        let position = position.into();
+        self.contains(position).then(|| &self.cells[self.index(position)])
+    }
+
+    pub fn get_mut(&mut self, position: impl Into<Position>) -> Option<&mut T> {
+        let position = position.into();
+        if self.contains(position) {
+            let index = self.index(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Looks up signed coordinates, wrapping around the edges like a torus.
+    /// Panics on an empty grid.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// The position `(dx, dy)` away from `position`, if it is still on the grid.
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// The up to 4 positions sharing an edge with `position`: north, east, south, west.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS4.iter().filter_map(move |&delta| self.offset(position, delta))
+    }
+
+    /// The up to 8 positions sharing an edge or a corner with `position`, row by row.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS8.iter().filter_map(move |&delta| self.offset(position, delta))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside a grid of width {}", self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.iter()
+            .enumerate()
+            .map(|(i, value)| ((i % self.width, i / self.width), value))
+    }
+
+    /// Positions of all cells matching `predicate`, row by row.
+    pub fn find_all<'a, P>(&'a self, mut predicate: P) -> impl Iterator<Item = Position> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a
+    {
+        self.iter().filter(move |(_, value)| predicate(value)).map(|(position, _)| position)
+    }
+
+    /// Position of the first cell matching `predicate`, row by row.
+    pub fn position<P>(&self, predicate: P) -> Option<Position>
+    where
+        P: FnMut(&T) -> bool
+    {
+        self.cells.iter().position(predicate).map(|i| (i % self.width, i / self.width))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U
+    {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self { width: self.height, height: self.width, cells }
+    }
+
+    /// Rotates the grid by a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| self[(x, y)].clone()))
+            .collect();
+        Self { width: self.height, height: self.width, cells }
+    }
+
+    /// Rotates the grid by a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone
+    {
+        let cells = (0..self.width).rev()
+            .flat_map(|x| (0..self.height).map(move |y| self[(x, y)].clone()))
+            .collect();
+        Self { width: self.height, height: self.width, cells }
+    }
+
+    fn index(&self, (x, y): Position) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T, P: Into<Position>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: P) -> &T {
+        let position = position.into();
+        assert!(self.contains(position), "{position:?} is outside a {}x{} grid", self.width, self.height);
+        &self.cells[Grid::index(self, position)]
+    }
+}
+
+impl<T, P: Into<Position>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, position: P) -> &mut T {
+        let position = position.into();
+        assert!(self.contains(position), "{position:?} is outside a {}x{} grid", self.width, self.height);
+        let index = Grid::index(self, position);
+        &mut self.cells[index]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, AOCError> {
+        Grid::parse(input, Ok)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                write!(f, "{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = grid("ab\ncd\nef");
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "abc\nab".parse::<Grid<char>>().unwrap_err().to_string();
+        assert!(error.contains("Expected 3 characters like the first line, found 2."));
+        assert!(error.contains("2 | ab"));
+
+        let error = Grid::parse("..\n.x", |c| match c {
+            '.' => Ok(false),
+            _ => Err(AOCError::parse(format!("'{c}' is not allowed.")))
+        }).unwrap_err().to_string();
+        assert!(error.contains(":2:2"));
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn test_wrapping_and_offset() {
+        let grid = grid("ab\ncd");
+        assert_eq!(*grid.get_wrapping(-1, -1), 'd');
+        assert_eq!(*grid.get_wrapping(4, 3), 'c');
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 1)), Some((1, 1)));
+        assert_eq!(grid.offset((1, 1), (1, 0)), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid("abc\ndef\nghi");
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((2, 2)).collect::<Vec<_>>(), vec![(1, 1), (2, 1), (1, 2)]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.columns().map(|column| column.collect::<String>()).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let grid = grid("#.#\n..#");
+        assert_eq!(grid.find_all(|c| *c == '#').collect::<Vec<_>>(), vec![(0, 0), (2, 0), (2, 1)]);
+        assert_eq!(grid.position(|c| *c == '.'), Some((1, 0)));
+    }
+}
@@ -0,0 +1,172 @@
+//! Where a day's input comes from. Files, stdin, text and bytes are all read
+//! the same way: gzip-compressed data is decompressed, CRLF line endings
+//! become LF and blank lines at the end are dropped, so a day only ever sees
+//! `\n` separated lines.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::MultiGzDecoder;
+
+use crate::errors::AOCError;
+use crate::InputSource;
+
+/// The first two bytes of gzip-compressed data.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input<'a> {
+    /// A file, which may be gzip-compressed.
+    Path(PathBuf),
+    Text(&'a str),
+    /// Raw bytes, which may be gzip-compressed.
+    Bytes(&'a [u8]),
+    Stdin
+}
+
+impl Input<'_> {
+    pub fn read(&self) -> Result<String, AOCError> {
+        let bytes = match self {
+            Input::Path(path) => fs::read(path).map_err(|error| AOCError::io(path, error))?,
+            Input::Text(text) => return Ok(normalize(text)),
+            Input::Bytes(bytes) => bytes.to_vec(),
+            Input::Stdin => {
+                let mut bytes = Vec::new();
+                io::stdin().read_to_end(&mut bytes)?;
+                bytes
+            }
+        };
+        decode(bytes).map_err(|error| match self.path() {
+            Some(path) => error.in_file(path),
+            None => error
+        })
+    }
+
+    /// The file the input is read from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Input::Path(path) => Some(path),
+            _ => None
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(text: &'a str) -> Self {
+        Input::Text(text)
+    }
+}
+
+impl<'a> From<&'a [u8]> for Input<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Input::Bytes(bytes)
+    }
+}
+
+impl From<&Path> for Input<'_> {
+    fn from(path: &Path) -> Self {
+        Input::Path(path.to_path_buf())
+    }
+}
+
+impl From<&PathBuf> for Input<'_> {
+    fn from(path: &PathBuf) -> Self {
+        Input::Path(path.clone())
+    }
+}
+
+impl From<PathBuf> for Input<'_> {
+    fn from(path: PathBuf) -> Self {
+        Input::Path(path)
+    }
+}
+
+impl From<&InputSource> for Input<'_> {
+    fn from(source: &InputSource) -> Self {
+        match source {
+            InputSource::File(path) => Input::Path(path.clone()),
+            InputSource::Stdin => Input::Stdin
+        }
+    }
+}
+
+/// Decompresses gzip data and checks the result is UTF-8 text.
+fn decode(bytes: Vec<u8>) -> Result<String, AOCError> {
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        MultiGzDecoder::new(&bytes[..]).read_to_end(&mut decompressed)
+            .map_err(|error| AOCError::parse(format!("Expected valid gzip data: {error}.")))?;
+        decompressed
+    } else {
+        bytes
+    };
+    let text = String::from_utf8(bytes)
+        .map_err(|error| AOCError::parse(format!("Expected UTF-8 text, found an invalid byte at offset {}.", error.utf8_error().valid_up_to())))?;
+    Ok(normalize(&text))
+}
+
+/// Turns CRLF line endings into LF and drops blank lines at the end, keeping
+/// the line break after the last line if there was one.
+pub fn normalize(text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let count = lines.len();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let mut normalized = lines.join("\n");
+    if !lines.is_empty() && (lines.len() < count || text.ends_with('\n')) {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use super::*;
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1 2\r\n3 4\r\n"), "1 2\n3 4\n");
+        assert_eq!(normalize("1 2\n3 4\n\n  \n"), "1 2\n3 4\n");
+        assert_eq!(normalize("1 2\n3 4"), "1 2\n3 4");
+        assert_eq!(normalize("1 2\n\n3 4\n"), "1 2\n\n3 4\n");
+        assert_eq!(normalize("\n\n"), "");
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(Input::from("a\r\nb\r\n\r\n").read().unwrap(), "a\nb\n");
+        assert_eq!(Input::from(&b"a\nb\n"[..]).read().unwrap(), "a\nb\n");
+        assert_eq!(Input::from(&gzip("a\r\nb\n\n")[..]).read().unwrap(), "a\nb\n");
+        assert!(Input::from(&[0x1f, 0x8b, 0][..]).read().unwrap_err().to_string().contains("Expected valid gzip data"));
+        assert!(Input::from(&[b'a', 0xff][..]).read().unwrap_err().to_string().contains("offset 1"));
+    }
+
+    #[test]
+    fn test_read_files() {
+        let directory = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let compressed = directory.join("input.txt.gz");
+        fs::write(&compressed, gzip("1 2\n3 4\n")).unwrap();
+        assert_eq!(Input::from(compressed.as_path()).read().unwrap(), "1 2\n3 4\n");
+
+        let binary = directory.join("binary.txt");
+        fs::write(&binary, [0xff]).unwrap();
+        assert!(Input::from(binary.clone()).read().unwrap_err().to_string().contains("binary.txt"));
+        assert!(matches!(Input::from(directory.join("missing.txt")).read(), Err(AOCError::Io { .. })));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
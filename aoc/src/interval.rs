@@ -0,0 +1,269 @@
+use std::fmt;
+use std::ops::{Add, Range};
+
+/// The half-open interval `start..end`, empty unless `start < end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The overlap of both intervals, `None` if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn shift(&self, by: T) -> Self
+    where
+        T: Add<Output = T>
+    {
+        Self::new(self.start + by, self.end + by)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self { start: range.start, end: range.end }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of values stored as sorted, disjoint and non-touching intervals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: Vec::new() }
+    }
+
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        self.intervals.push(interval.into());
+        self.normalize();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(value))
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The values in `self` which are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut others = other.intervals.iter().peekable();
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            while let Some(cut) = others.peek() {
+                if cut.end <= start {
+                    others.next();
+                    continue;
+                }
+                if cut.start >= interval.end {
+                    break;
+                }
+                if cut.start > start {
+                    intervals.push(Interval::new(start, cut.start));
+                }
+                start = cut.end;
+                if cut.end > interval.end {
+                    break;
+                }
+                others.next();
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+        Self { intervals }
+    }
+
+    fn normalize(&mut self) {
+        self.intervals.retain(|interval| !interval.is_empty());
+        self.intervals.sort();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval)
+            }
+        }
+        self.intervals = merged;
+    }
+}
+
+impl<T: Copy + Ord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord, I: Into<Interval<T>>> FromIterator<I> for IntervalSet<T> {
+    fn from_iter<Iter: IntoIterator<Item = I>>(iter: Iter) -> Self {
+        let mut set = Self { intervals: iter.into_iter().map(Into::into).collect() };
+        set.normalize();
+        set
+    }
+}
+
+/// A piecewise shift: values inside a source interval move by its offset,
+/// all other values map to themselves. Where sources overlap the one
+/// inserted first wins.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    shifts: Vec<(Interval<T>, T)>
+}
+
+impl<T: Copy + Ord + Add<Output = T>> RangeMap<T> {
+    pub fn new() -> Self {
+        Self { shifts: Vec::new() }
+    }
+
+    pub fn insert(&mut self, source: impl Into<Interval<T>>, shift: T) {
+        self.shifts.push((source.into(), shift));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shifts.is_empty()
+    }
+
+    pub fn map(&self, value: T) -> T {
+        self.shifts.iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(_, shift)| value + *shift)
+    }
+
+    /// Maps every value of `set` at once, one interval per piece of the map.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut remaining = set.clone();
+        let mut mapped: Vec<Interval<T>> = Vec::new();
+        for (source, shift) in &self.shifts {
+            if remaining.is_empty() {
+                break;
+            }
+            let source: IntervalSet<T> = [*source].into_iter().collect();
+            mapped.extend(remaining.intersection(&source).iter().map(|interval| interval.shift(*shift)));
+            remaining = remaining.difference(&source);
+        }
+        mapped.extend(remaining.intervals);
+        mapped.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> IntervalSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::new(2, 5);
+        assert!(interval.contains(2));
+        assert!(!interval.contains(5));
+        assert_eq!(interval.intersection(&Interval::new(4, 9)), Some(Interval::new(4, 5)));
+        assert_eq!(interval.intersection(&Interval::new(5, 9)), None);
+        assert!(Interval::new(3, 3).is_empty());
+        assert_eq!(interval.shift(-2).to_string(), "0..3");
+    }
+
+    #[test]
+    fn test_normalize() {
+        let merged = set(&[5..7, 0..2, 2..3, 6..10, 4..4]);
+        assert_eq!(merged, set(&[0..3, 5..10]));
+        assert_eq!(merged.min(), Some(0));
+        assert!(merged.contains(9));
+        assert!(!merged.contains(3));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = set(&[5..25, 28..40]);
+        assert_eq!(a.union(&b), IntervalSet::from_iter([Interval::new(0, 40)]));
+        assert_eq!(a.intersection(&b), set(&[5..10, 20..25, 28..30]));
+        assert_eq!(a.difference(&b), set(&[0..5, 25..28]));
+        assert_eq!(b.difference(&a), set(&[10..20, 30..40]));
+        assert_eq!(a.difference(&IntervalSet::from_iter([Interval::new(-5, 50)])), set(&[]));
+        assert_eq!(a.difference(&set(&[2..3, 4..5])), set(&[0..2, 3..4, 5..10, 20..30]));
+    }
+
+    #[test]
+    fn test_set_operations_match_values() {
+        let a = set(&[0..4, 6..9, 12..13, 15..20]);
+        let b = set(&[2..7, 8..16, 18..19]);
+        for value in -1..22 {
+            assert_eq!(a.union(&b).contains(value), a.contains(value) || b.contains(value));
+            assert_eq!(a.intersection(&b).contains(value), a.contains(value) && b.contains(value));
+            assert_eq!(a.difference(&b).contains(value), a.contains(value) && !b.contains(value));
+        }
+    }
+
+    #[test]
+    fn test_range_map() {
+        let mut map = RangeMap::new();
+        map.insert(98..100, -48);
+        map.insert(50..98, 2);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(10), 10);
+
+        let mapped = map.map_set(&set(&[40..60, 95..105]));
+        for value in [40..60, 95..105].into_iter().flatten() {
+            assert!(mapped.contains(map.map(value)));
+        }
+        assert_eq!(mapped, set(&[40..50, 50..52, 52..62, 97..100, 100..105]));
+    }
+}
@@ -0,0 +1,197 @@
+//! Just enough JSON to write results for scripts, see `--format json`.
+
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+use crate::errors::AOCError;
+use crate::Part;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(u128),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>)
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<u32> for Json {
+    fn from(value: u32) -> Self {
+        Self::Number(value as u128)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Self::Number(value as u128)
+    }
+}
+
+impl From<Duration> for Json {
+    /// In nanoseconds.
+    fn from(value: Duration) -> Self {
+        Self::Number(value.as_nanos())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+impl From<&AOCError> for Json {
+    /// The kind and full message of the error, and where in the input it
+    /// happened for parse errors.
+    fn from(error: &AOCError) -> Self {
+        let mut fields = vec![("kind", Json::from(error.kind())), ("message", Json::from(error.to_string()))];
+        if let AOCError::Parse(error) = error {
+            fields.extend([
+                ("file", Json::from(error.file.as_ref().map(|file| file.display().to_string()))),
+                ("line", Json::from(error.line)),
+                ("column", Json::from(error.column))
+            ]);
+        }
+        Self::Object(fields)
+    }
+}
+
+impl fmt::Display for Json {
+    /// Compact, on a single line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Number(value) => write!(f, "{value}"),
+            Self::String(value) => write_string(f, value),
+            Self::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            },
+            Self::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?
+        }
+    }
+    f.write_char('"')
+}
+
+/// The outcome of one part of a day, as written by the day binaries and
+/// `aoc run`. Everything known about a part that failed is kept, so a day
+/// whose input is missing still reports its day and part.
+#[derive(Clone, Copy, Debug)]
+pub struct PartReport<'a> {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<&'a str, &'a AOCError>,
+    /// The Rust type of the answer, like `u64`.
+    pub answer_type: Option<&'a str>,
+    /// Where the input was read from, `-` for stdin.
+    pub input: Option<&'a str>,
+    pub input_hash: Option<&'a str>,
+    pub parse_time: Option<Duration>,
+    pub part_time: Option<Duration>
+}
+
+impl PartReport<'_> {
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("day", Json::from(self.day)),
+            ("part", Json::from(self.part.number())),
+            ("answer", Json::from(self.answer.ok())),
+            ("answer_type", Json::from(self.answer_type)),
+            ("input", Json::from(self.input)),
+            ("input_hash", Json::from(self.input_hash)),
+            ("timings", Json::Object(vec![
+                ("parse_ns", Json::from(self.parse_time)),
+                ("part_ns", Json::from(self.part_time))
+            ])),
+            ("error", self.answer.err().map_or(Json::Null, Json::from))
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let json = Json::Object(vec![
+            ("text", Json::from("a \"quote\"\\\n\u{1}é")),
+            ("list", Json::Array(vec![Json::from(1_u32), Json::Null, Json::Bool(false)])),
+            ("empty", Json::Object(Vec::new()))
+        ]);
+        assert_eq!(json.to_string(), r#"{"text":"a \"quote\"\\\n\u0001é","list":[1,null,false],"empty":{}}"#);
+    }
+
+    #[test]
+    fn test_part_report() {
+        let report = PartReport {
+            day: 5,
+            part: Part::Two,
+            answer: Ok("46"),
+            answer_type: Some("i64"),
+            input: Some("day5/input.txt"),
+            input_hash: Some("0123456789abcdef"),
+            parse_time: Some(Duration::from_micros(12)),
+            part_time: Some(Duration::from_nanos(345))
+        };
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"day":5,"part":2,"answer":"46","answer_type":"i64","input":"day5/input.txt","input_hash":"0123456789abcdef","timings":{"parse_ns":12000,"part_ns":345},"error":null}"#
+        );
+
+        let error = AOCError::parse("'x' is not a number.").at_line(2, "1 x").at_column(3).in_file(Path::new("in.txt"));
+        let failed = PartReport { answer: Err(&error), parse_time: None, part_time: None, ..report };
+        let json = failed.to_json().to_string();
+        assert!(json.contains(r#""answer":null"#));
+        assert!(json.contains(r#""timings":{"parse_ns":null,"part_ns":null}"#));
+        assert!(json.contains(r#""error":{"kind":"parse","message":"'x' is not a number.\n --> in.txt:2:3"#));
+        assert!(json.ends_with(r#""file":"in.txt","line":2,"column":3}}"#));
+    }
+}
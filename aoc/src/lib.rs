@@ -1,67 +1,85 @@
-use std::io::BufRead;
 use std::path::PathBuf;
-use std::{env, fs::File, io, path::Path, process};
+use std::{fs, path::Path, process};
 
 use errors::AOCError;
+pub mod args;
 pub mod errors;
+pub mod examples;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod json;
+pub mod math;
+pub mod parse;
+pub mod solution;
+pub mod toml;
+pub mod viz;
 
-#[derive(PartialEq)]
+pub use args::{get_args, Format, InputSource, Options};
+pub use input::Input;
+pub use solution::{run, Answer, Runnable, SharedInput, Solution, Timings};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Part {
     One,
     Two
 }
 
-pub struct Options {
-    pub part: Part,
-    pub input: PathBuf
-}
-
-pub fn get_args() -> Options {
-    let args: Vec<String> = env::args().collect();
-    let program_name = &args[0];
-
-    if args.len() < 2 || args.len() > 3 || args[1] == "--help" {
-        exit_with_usage(program_name);
+impl Part {
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
     }
+}
 
-    let part = if args[1] == "--part2" {Part::Two} else {Part::One};
-    if (part == Part::Two && args.len() != 3) || (part == Part::One && args.len() != 2) {
-        exit_with_usage(program_name);
-    }
+/// Reads an input from a path, text, bytes or stdin, see [`Input`].
+pub fn get_input_buffer<'a>(input: impl Into<Input<'a>>) -> Result<String, AOCError> {
+    input.into().read()
+}
 
-    let path_argument_position = if part == Part::One {1} else {2};
-    let input_path = PathBuf::from(&args[path_argument_position]);
-    if !input_path.exists() {
-        eprintln!("Input file does not exist: {}", input_path.display());
-        exit_with_usage(&args[0]);
-    }
+pub fn read_input(path: &Path) -> Result<String, AOCError> {
+    Input::from(path).read()
+}
 
-    Options {
-        part: part,
-        input: input_path
-    }
+/// A short fingerprint of an input, stable across runs and Rust versions
+/// (64 bit FNV-1a), to tell inputs apart without storing them.
+pub fn hash_input(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
 }
 
-pub fn get_input_buffer(path: &PathBuf) -> io::Lines<io::BufReader<File>> {
-    if let Ok(lines) = read_lines(path) {
-        return lines
-    }
+pub fn read_source(source: &InputSource) -> Result<String, AOCError> {
+    Input::from(source).read()
+}
 
-    eprintln!("Could read file: {path:?}");
-    process::exit(1);
+pub fn workspace_dir() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
 }
 
-fn read_lines(path: &Path) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let file = File::open(path)?;
-    Ok(io::BufReader::new(file).lines())
+/// The `number`th (starting at 1) example file in `dayN/tests`, ordered by name.
+pub fn example_path(day: u32, number: usize) -> Result<PathBuf, AOCError> {
+    let directory = workspace_dir().join(format!("day{day}")).join("tests");
+    let mut examples: Vec<PathBuf> = fs::read_dir(&directory)
+        .map_err(|error| AOCError::io(&directory, error))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    examples.sort();
+
+    let count = examples.len();
+    number.checked_sub(1)
+        .and_then(|index| examples.into_iter().nth(index))
+        .ok_or(AOCError::invalid_argument(format!("Day {day} has no example {number}, there are {count}")))
 }
 
 pub fn exit_with_error(error: AOCError) {
     eprintln!("{error}");
     process::exit(1);
 }
-
-fn exit_with_usage(program_name: &str) {
-    println!("Usage: [--part2] {program_name} INPUT_FILE");
-    process::exit(1);
-}
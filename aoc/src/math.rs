@@ -0,0 +1,164 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {a} else {gcd(b, a % b)}
+}
+
+/// Least common multiple, 0 if either number is 0.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {0} else {a / gcd(a, b) * b}
+}
+
+/// Greatest common divisor of all numbers, 0 for none.
+pub fn gcd_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all numbers, 1 for none.
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        if a < 0 {(-a, -1, 0)} else {(a, 1, 0)}
+    } else {
+        let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+        (g, y, x - a.div_euclid(b) * y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`, if `a` and `modulus` are coprime.
+/// `None` for a `modulus` below 1.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base` to the power of `exponent`, modulo `modulus`. `None` for a `modulus` of 0.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> Option<u64> {
+    match modulus {
+        0 => return None,
+        1 => return Some(0),
+        _ => ()
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result: u128 = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    Some(result as u64)
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    let mut root = (n as f64).sqrt() as u64;
+    while root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+    while (root + 1).checked_mul(root + 1).is_some_and(|square| square <= n) {
+        root += 1;
+    }
+    root
+}
+
+/// Solves `x = residue (mod modulus)` for every pair with the Chinese
+/// Remainder Theorem. The moduli don't have to be coprime. Returns the
+/// smallest non-negative solution and the modulus all solutions share,
+/// `None` if the congruences contradict each other, a modulus is below 1 or
+/// the shared modulus doesn't fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences.into_iter().try_fold((0, 1), |(residue, modulus), (other_residue, other_modulus)| {
+        if other_modulus <= 0 {
+            return None;
+        }
+        let (g, x, _) = extended_gcd(modulus, other_modulus);
+        let difference = other_residue as i128 - residue as i128;
+        if difference % g as i128 != 0 {
+            return None;
+        }
+        let combined = (modulus / g).checked_mul(other_modulus)?;
+        let step = difference / g as i128 * x as i128 % (other_modulus / g) as i128;
+        let solution = (residue as i128 + modulus as i128 * step).rem_euclid(combined as i128);
+        Some((solution as i64, combined))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm_all([2, 3, 4]), 12);
+        assert_eq!(lcm_all([]), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-12, 18), (7, 0), (0, 5), (17, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64, "{a} {b}");
+            assert_eq!(a * x + b * y, g, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -11), None);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(3, 0, 7), Some(1));
+        assert_eq!(mod_pow(3, 5, 1), Some(0));
+        assert_eq!(mod_pow(3, 5, 0), None);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), Some(254_368_884));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..1000 {
+            let root = isqrt(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "{n}");
+        }
+        assert_eq!(isqrt(u64::MAX), 4_294_967_295);
+        assert_eq!(isqrt(999_999_999_999_999_999), 999_999_999);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(0, 6), (0, 4)]), Some((0, 12)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(1, 3), (0, 0)]), None);
+        assert_eq!(crt([(1, -3)]), None);
+        let large = 4_000_000_007;
+        assert_eq!(crt([(1, large), (2, large + 2)]), None);
+        assert_eq!(crt([(i64::MAX - 1, i64::MAX), (i64::MIN, 1)]), Some((i64::MAX - 1, i64::MAX)));
+
+        for a in 1..12 {
+            for b in 1..12 {
+                for (ra, rb) in [(0, 0), (a - 1, b - 1), (a / 2, b / 3)] {
+                    let brute = (0..a * b).find(|x| x % a == ra && x % b == rb);
+                    assert_eq!(crt([(ra, a), (rb, b)]).map(|(x, _)| x), brute, "{ra} mod {a}, {rb} mod {b}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::str::FromStr;
+
+use crate::errors::AOCError;
+
+pub type Parsed<T> = Result<T, AOCError>;
+
+/// Reads a line from left to right. Every parser skips the spaces in front
+/// of what it reads, and errors point at the column where it failed.
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    line: &'a str,
+    rest: &'a str
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self { line, rest: line }
+    }
+
+    /// What is left to read.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Whether only whitespace is left.
+    pub fn is_done(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    /// A parse error pointing at the next character to read.
+    pub fn error(&self, message: impl Into<String>) -> AOCError {
+        AOCError::parse(message).at_column_of(self.line, self.rest.trim_start())
+    }
+
+    /// Reads exactly `expected`.
+    pub fn literal(&mut self, expected: &str) -> Parsed<()> {
+        if self.try_literal(expected) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{expected}'.")))
+        }
+    }
+
+    /// Reads `expected` if it comes next, leaving the cursor alone otherwise.
+    pub fn try_literal(&mut self, expected: &str) -> bool {
+        match self.rest.trim_start().strip_prefix(expected) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Reads a run of letters and digits.
+    pub fn word(&mut self) -> Parsed<&'a str> {
+        let word = self.take_while(|c| c.is_alphanumeric());
+        if word.is_empty() {
+            return Err(self.error("Expected a word."));
+        }
+        Ok(word)
+    }
+
+    /// Reads a number without a sign.
+    pub fn unsigned<T: FromStr>(&mut self) -> Parsed<T> {
+        let token = self.take_while(|c| c.is_alphanumeric());
+        self.number(token)
+    }
+
+    /// Reads a number with an optional `+` or `-` in front.
+    pub fn signed<T: FromStr>(&mut self) -> Parsed<T> {
+        let mut first = true;
+        let token = self.take_while(|c| {
+            let sign = first && (c == '-' || c == '+');
+            first = false;
+            sign || c.is_alphanumeric()
+        });
+        self.number(token)
+    }
+
+    /// Reads numbers separated by whitespace, up to the end of the line or
+    /// the first character which can't start a number.
+    pub fn numbers<T: FromStr>(&mut self) -> Parsed<Vec<T>> {
+        let mut numbers = Vec::new();
+        while self.rest.trim_start().starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '+') {
+            numbers.push(self.signed()?);
+        }
+        Ok(numbers)
+    }
+
+    /// Reads one or more items with `separator` between them.
+    pub fn separated<T, F>(&mut self, separator: &str, mut item: F) -> Parsed<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> Parsed<T>
+    {
+        let mut items = vec![item(self)?];
+        while self.try_literal(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Reads `label` followed by whatever `value` reads, like `Game 5`.
+    pub fn labelled<T, F>(&mut self, label: &str, value: F) -> Parsed<T>
+    where
+        F: FnOnce(&mut Self) -> Parsed<T>
+    {
+        self.literal(label)?;
+        value(self)
+    }
+
+    /// Reads everything up to `separator` as the key, then the value after
+    /// it, like `Time: 7 15 30`. The key is trimmed.
+    pub fn key_value<T, F>(&mut self, separator: &str, value: F) -> Parsed<(&'a str, T)>
+    where
+        F: FnOnce(&mut Self) -> Parsed<T>
+    {
+        let key = self.until(separator)?.trim();
+        self.literal(separator)?;
+        Ok((key, value(self)?))
+    }
+
+    /// Reads everything up to, but not including, `delimiter`.
+    pub fn until(&mut self, delimiter: &str) -> Parsed<&'a str> {
+        let Some(end) = self.rest.find(delimiter) else {
+            let mut end = *self;
+            end.rest = &self.line[self.line.len()..];
+            return Err(end.error(format!("Expected '{delimiter}'.")));
+        };
+        let text = &self.rest[..end];
+        self.rest = &self.rest[end..];
+        Ok(text)
+    }
+
+    /// Fails unless only whitespace is left.
+    pub fn end(&mut self) -> Parsed<()> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.error(format!("Unexpected '{}'.", self.rest.trim())))
+        }
+    }
+
+    fn take_while<P: FnMut(char) -> bool>(&mut self, mut predicate: P) -> &'a str {
+        self.rest = self.rest.trim_start();
+        let end = self.rest.find(|c| !predicate(c)).unwrap_or(self.rest.len());
+        let (token, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        token
+    }
+
+    fn number<T: FromStr>(&self, token: &'a str) -> Parsed<T> {
+        if token.is_empty() {
+            return Err(self.error("Expected a number."));
+        }
+        token.parse::<T>()
+            .map_err(|_| AOCError::parse(format!("'{token}' is not a number.")).at_column_of(self.line, token))
+    }
+}
+
+/// Runs `parser` on the whole of `line`, failing if anything is left over.
+pub fn parse_line<'a, T, F>(line: &'a str, parser: F) -> Parsed<T>
+where
+    F: FnOnce(&mut Cursor<'a>) -> Parsed<T>
+{
+    let mut cursor = Cursor::new(line);
+    let value = parser(&mut cursor)?;
+    cursor.end()?;
+    Ok(value)
+}
+
+/// Runs `parser` on every line, adding the line to errors.
+pub fn parse_lines<'a, T, F>(input: &'a str, mut parser: F) -> Parsed<Vec<T>>
+where
+    F: FnMut(&mut Cursor<'a>) -> Parsed<T>
+{
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line, &mut parser).map_err(|error| error.at_line(i + 1, line)))
+        .collect()
+}
+
+/// A block of consecutive non-blank lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section<'a> {
+    /// Number of the section's first line in the whole input, starting at 1.
+    pub first_line: usize,
+    pub lines: Vec<&'a str>
+}
+
+impl<'a> Section<'a> {
+    /// The lines with their numbers in the whole input.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines.iter().enumerate().map(|(i, line)| (self.first_line + i, *line))
+    }
+
+    /// The first line and the section of the lines after it, for sections
+    /// which start with a header like `seed-to-soil map:`.
+    pub fn split_header(&self) -> Option<(&'a str, Section<'a>)> {
+        let (header, lines) = self.lines.split_first()?;
+        Some((header, Section { first_line: self.first_line + 1, lines: lines.to_vec() }))
+    }
+
+    /// Runs `parser` on every line of the section, adding the line to errors.
+    pub fn parse_lines<T, F>(&self, mut parser: F) -> Parsed<Vec<T>>
+    where
+        F: FnMut(&mut Cursor<'a>) -> Parsed<T>
+    {
+        self.numbered()
+            .map(|(number, line)| parse_line(line, &mut parser).map_err(|error| error.at_line(number, line)))
+            .collect()
+    }
+}
+
+/// Splits the input at blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut current: Option<Section> = None;
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+        } else {
+            current.get_or_insert(Section { first_line: i + 1, lines: Vec::new() }).lines.push(line);
+        }
+    }
+    sections.extend(current);
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error<T: std::fmt::Debug>(result: Parsed<T>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    fn column<T: std::fmt::Debug>(result: Parsed<T>) -> Option<usize> {
+        match result.unwrap_err() {
+            AOCError::Parse(error) => error.column,
+            error => panic!("expected a parse error, got {error:?}")
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse_line(" 42", Cursor::unsigned::<u32>).unwrap(), 42);
+        assert_eq!(parse_line("-7", Cursor::signed::<i64>).unwrap(), -7);
+        assert_eq!(parse_line("1 -2  +3", Cursor::numbers::<i32>).unwrap(), vec![1, -2, 3]);
+        assert_eq!(parse_line("", Cursor::numbers::<i32>).unwrap(), Vec::<i32>::new());
+
+        assert!(error(parse_line("1 2x 3", Cursor::numbers::<u32>)).starts_with("'2x' is not a number."));
+        assert_eq!(column(parse_line("1 2x 3", Cursor::numbers::<u32>)), Some(3));
+        assert!(error(parse_line("-1", Cursor::unsigned::<u32>)).starts_with("Expected a number."));
+        assert!(error(parse_line("-1", Cursor::numbers::<u32>)).starts_with("'-1' is not a number."));
+    }
+
+    #[test]
+    fn test_literals_and_lists() {
+        let mut cursor = Cursor::new("Game 5: 1 red, 2 blue; 3 green");
+        assert_eq!(cursor.labelled("Game", Cursor::unsigned::<u32>).unwrap(), 5);
+        cursor.literal(":").unwrap();
+        let draws = cursor.separated(";", |cursor| cursor.separated(",", |cursor| {
+            Ok((cursor.unsigned::<u32>()?, cursor.word()?))
+        })).unwrap();
+        assert_eq!(draws, vec![vec![(1, "red"), (2, "blue")], vec![(3, "green")]]);
+        assert!(cursor.is_done());
+
+        let node = |cursor: &mut Cursor| cursor.labelled("AAA", |cursor| cursor.literal("="));
+        assert!(error(parse_line("AAA (BBB)", node)).starts_with("Expected '='."));
+        assert_eq!(column(parse_line("AAA (BBB)", node)), Some(5));
+        assert!(error(parse_line("1 2", Cursor::unsigned::<u32>)).starts_with("Unexpected '2'."));
+        assert!(error(parse_line(", x", Cursor::word)).starts_with("Expected a word."));
+    }
+
+    #[test]
+    fn test_key_value() {
+        let (key, values) = parse_line("Time:      7  15   30", |cursor| cursor.key_value(":", Cursor::numbers::<u32>)).unwrap();
+        assert_eq!(key, "Time");
+        assert_eq!(values, vec![7, 15, 30]);
+
+        let time = |cursor: &mut Cursor| cursor.key_value(":", Cursor::numbers::<u32>).map(|(_, values)| values);
+        assert!(error(parse_line("Time 7", time)).starts_with("Expected ':'."));
+        assert_eq!(column(parse_line("Time 7", time)), Some(7));
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "seeds: 1 2\n\na map:\n1 2 3\n\n\nb map:\n4 5 6\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1], Section { first_line: 3, lines: vec!["a map:", "1 2 3"] });
+        assert_eq!(sections[2].numbered().collect::<Vec<_>>(), vec![(7, "b map:"), (8, "4 5 6")]);
+        let (header, body) = sections[2].split_header().unwrap();
+        assert_eq!((header, body.first_line, body.lines), ("b map:", 8, vec!["4 5 6"]));
+
+        let message = error(parse_lines("1 2\n3 x", Cursor::numbers::<u32>));
+        assert!(message.contains(":2:3"));
+        assert!(message.contains("2 | 3 x"));
+    }
+}
@@ -0,0 +1,236 @@
+use std::any::{type_name, Any};
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use crate::args::{get_args, usage, Format};
+use crate::errors::AOCError;
+use crate::examples::Example;
+use crate::json::{Json, PartReport};
+use crate::viz::Player;
+use crate::{example_path, exit_with_error, hash_input, read_source, Input, InputSource, Part};
+
+/// A day's puzzle: parses the raw input once and solves both parts on it.
+pub trait Solution: Default {
+    const DAY: u32;
+    /// Usage of the extra arguments accepted by [`Solution::with_args`].
+    const ARGS: &'static str = "";
+
+    /// Shared between threads when the runner solves both parts at once.
+    type Input: Send + Sync + 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Builds the solution from the day specific command line arguments.
+    fn with_args(args: &[String]) -> Result<Self, AOCError> {
+        match args.first() {
+            Some(arg) => Err(AOCError::invalid_argument(format!("Unexpected argument '{arg}'"))),
+            None => Ok(Self::default())
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError>;
+
+    /// Reads `input`, which may also be a file, bytes or stdin, and parses it.
+    fn parse_input<'a>(&self, input: impl Into<Input<'a>>) -> Result<Self::Input, AOCError> {
+        let input = input.into();
+        let text = input.read()?;
+        self.parse(&text).map_err(|error| match input.path() {
+            Some(path) => error.in_file(path),
+            None => error
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AOCError>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AOCError>;
+
+    /// Shows how the answers come about with `--visualize`, see [`crate::viz`].
+    fn visualize(&self, _input: &Self::Input, _player: &mut Player) -> Result<(), AOCError> {
+        Err(AOCError::invalid_argument(format!("Day {} has no visualization", Self::DAY)))
+    }
+}
+
+/// Shared `main` for the day binaries.
+pub fn run<S: Solution>() {
+    let program_name = env::args().next().unwrap_or(format!("day{}", S::DAY));
+    if env::args().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", usage(&program_name, S::ARGS));
+        return;
+    }
+
+    let arguments = get_args().and_then(|options| {
+        let solution = S::with_args(&options.extra)?;
+        Ok((options, solution))
+    });
+    let (options, solution) = match arguments {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("{}", usage(&program_name, S::ARGS));
+            process::exit(1);
+        }
+    };
+
+    let source = match (options.input, options.example) {
+        (Some(source), _) => Ok(source),
+        (None, Some(number)) => example_path(S::DAY, number).map(InputSource::File),
+        (None, None) => Err(AOCError::invalid_argument("No input given"))
+    };
+    if let Some(playback) = options.visualize {
+        let visualized = source.and_then(|source| {
+            let input = solution.parse_input(&source)?;
+            solution.visualize(&input, &mut Player::terminal(playback))
+        });
+        if let Err(error) = visualized {
+            exit_with_error(error);
+        }
+        return;
+    }
+
+    let input = source.as_ref().ok().map(|source| match source {
+        InputSource::File(path) => path.display().to_string(),
+        InputSource::Stdin => String::from("-")
+    });
+    let solved = source.and_then(|source| {
+        let text = read_source(&source)?;
+        let (answers, timings) = solution.solve_timed(&text, &options.parts).map_err(|error| match &source {
+            InputSource::File(path) => error.in_file(path),
+            InputSource::Stdin => error
+        })?;
+        Ok((hash_input(&text), answers, timings))
+    });
+
+    if options.format == Format::Text {
+        let answers = match solved {
+            Ok((_, answers, _)) => answers,
+            Err(error) => return exit_with_error(error)
+        };
+        for answer in answers {
+            match answer {
+                Ok(answer) => println!("{answer}"),
+                Err(error) => exit_with_error(error)
+            }
+        }
+        return;
+    }
+
+    let answer_types: Vec<String> = options.parts.iter().map(|part| solution.answer_type(*part)).collect();
+    let reports: Vec<PartReport> = options.parts.iter().enumerate()
+        .map(|(i, &part)| {
+            let report = PartReport {
+                day: S::DAY,
+                part,
+                answer: Ok(""),
+                answer_type: Some(&answer_types[i]),
+                input: input.as_deref(),
+                input_hash: None,
+                parse_time: None,
+                part_time: None
+            };
+            match &solved {
+                Ok((hash, answers, timings)) => PartReport {
+                    answer: answers[i].as_deref(),
+                    input_hash: Some(hash),
+                    parse_time: Some(timings.parse),
+                    part_time: timings.parts.get(i).copied(),
+                    ..report
+                },
+                Err(error) => PartReport { answer: Err(error), ..report }
+            }
+        })
+        .collect();
+    let values = reports.iter().map(PartReport::to_json);
+    match options.format {
+        Format::Json => println!("{}", Json::Array(values.collect())),
+        _ => values.for_each(|value| println!("{value}"))
+    }
+    if reports.iter().any(|report| report.answer.is_err()) {
+        process::exit(1);
+    }
+}
+
+/// A rendered answer, or the error a part failed with.
+pub type Answer = Result<String, AOCError>;
+
+/// How long parsing and each of the solved parts took.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub parts: Vec<Duration>
+}
+
+/// An input parsed by [`Runnable::parse_shared`], which any thread can solve
+/// parts on.
+pub type SharedInput = Arc<dyn Any + Send + Sync>;
+
+/// Object safe view of a [`Solution`], so days with different input and
+/// answer types can be kept in one registry.
+pub trait Runnable {
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Answer>, AOCError> {
+        self.solve_timed(input, parts).map(|(answers, _)| answers)
+    }
+
+    fn solve_timed(&self, input: &str, parts: &[Part]) -> Result<(Vec<Answer>, Timings), AOCError>;
+
+    fn parse_shared(&self, input: &str) -> Result<SharedInput, AOCError>;
+
+    /// Solves `part` on an input from [`Runnable::parse_shared`] of the same day.
+    fn solve_part(&self, input: &SharedInput, part: Part) -> Answer;
+
+    /// The name of the type a part answers with, like `u64`.
+    fn answer_type(&self, part: Part) -> String;
+
+    /// Checks the answers to an example, see [`Example::check`].
+    fn check_example(&self, example: &Example) -> Result<(), String>;
+}
+
+impl<S: Solution> Runnable for S {
+    fn solve_timed(&self, input: &str, parts: &[Part]) -> Result<(Vec<Answer>, Timings), AOCError> {
+        let start = Instant::now();
+        let input = self.parse(input)?;
+        let mut timings = Timings { parse: start.elapsed(), parts: Vec::new() };
+
+        let answers = parts.iter()
+            .map(|part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => self.part1(&input).map(|answer| answer.to_string()),
+                    Part::Two => self.part2(&input).map(|answer| answer.to_string())
+                };
+                timings.parts.push(start.elapsed());
+                answer
+            })
+            .collect();
+        Ok((answers, timings))
+    }
+
+    fn parse_shared(&self, input: &str) -> Result<SharedInput, AOCError> {
+        Ok(Arc::new(self.parse(input)?))
+    }
+
+    fn solve_part(&self, input: &SharedInput, part: Part) -> Answer {
+        let input = input.downcast_ref::<S::Input>()
+            .ok_or(AOCError::invalid_argument(format!("The input wasn't parsed by day {}", S::DAY)))?;
+        match part {
+            Part::One => self.part1(input).map(|answer| answer.to_string()),
+            Part::Two => self.part2(input).map(|answer| answer.to_string())
+        }
+    }
+
+    fn answer_type(&self, part: Part) -> String {
+        let name = match part {
+            Part::One => type_name::<S::Answer1>(),
+            Part::Two => type_name::<S::Answer2>()
+        };
+        // Without module paths, `Vec<String>` rather than `alloc::vec::Vec<alloc::string::String>`.
+        name.split_inclusive(['<', '>', ',', '(', ')', '[', ']', ';', '&', ' '])
+            .map(|piece| piece.rsplit("::").next().unwrap_or(piece))
+            .collect()
+    }
+
+    fn check_example(&self, example: &Example) -> Result<(), String> {
+        example.check::<S>()
+    }
+}
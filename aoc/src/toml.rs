@@ -0,0 +1,179 @@
+//! Reads the few TOML files of the workspace: the answers next to examples,
+//! the known answers of `aoc verify` and the runner's config. Only `[section]`
+//! headers and `key = value` lines with `#` comments are understood. A value
+//! is a string in double quotes, with `\"`, `\\`, `\n` and `\t` escapes, or a
+//! bare word like a number.
+
+use std::mem;
+
+use crate::errors::AOCError;
+
+/// The entries under a `[header]`, or those before the first header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section<'a> {
+    /// `None` for the entries before the first header.
+    pub header: Option<Key<'a>>,
+    pub entries: Vec<Entry<'a>>
+}
+
+/// The name of a key or section header, and the line it is on for errors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Key<'a> {
+    pub name: &'a str,
+    line: &'a str,
+    number: usize
+}
+
+impl Key<'_> {
+    /// A parse error pointing at the key.
+    pub fn error(&self, message: impl Into<String>) -> AOCError {
+        AOCError::parse(message).at_column_of(self.line, self.name).at_line(self.number, self.line)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry<'a> {
+    pub key: Key<'a>,
+    /// Without quotes, escapes or a trailing comment.
+    pub value: String,
+    pub quoted: bool,
+    /// Where the value starts in the line.
+    raw: &'a str
+}
+
+impl Entry<'_> {
+    /// A parse error pointing at the value.
+    pub fn value_error(&self, message: impl Into<String>) -> AOCError {
+        AOCError::parse(message).at_column_of(self.key.line, self.raw).at_line(self.key.number, self.key.line)
+    }
+}
+
+/// Splits `contents` into sections, the first of which holds the entries
+/// before any header and may be empty.
+pub fn parse(contents: &str) -> Result<Vec<Section<'_>>, AOCError> {
+    let mut sections = Vec::new();
+    let mut current = Section { header: None, entries: Vec::new() };
+    for (i, line) in contents.lines().enumerate() {
+        let number = i + 1;
+        let error = |message: &str, at: &str| AOCError::parse(message).at_column_of(line, at).at_line(number, line);
+        let content = line.trim_start();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = content.strip_prefix('[') {
+            let (name, rest) = header.split_once(']').ok_or(error("Expected a closing ']'.", content))?;
+            end_of_line(rest).map_err(|rest| error("Expected a comment or the end of the line.", rest))?;
+            let header = Key { name: name.trim(), line, number };
+            sections.push(mem::replace(&mut current, Section { header: Some(header), entries: Vec::new() }));
+            continue;
+        }
+
+        let (key, raw) = content.split_once('=').ok_or(error("Expected 'key = value'.", content))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(error("Expected a key before '='.", content));
+        }
+        let raw = raw.trim_start();
+        let (value, quoted, rest) = match raw.strip_prefix('"') {
+            Some(string) => {
+                let (value, rest) = unquote(string).ok_or(error("Expected a closing '\"'.", raw))?;
+                (value, true, rest)
+            },
+            None => {
+                let value = raw.split('#').next().unwrap_or(raw).trim_end();
+                (value.to_owned(), false, &raw[value.len()..])
+            }
+        };
+        end_of_line(rest).map_err(|rest| error("Expected a comment or the end of the line.", rest))?;
+        current.entries.push(Entry { key: Key { name: key, line, number }, value, quoted, raw });
+    }
+    sections.push(current);
+    Ok(sections)
+}
+
+/// Accepts whitespace and a comment, returns anything else.
+fn end_of_line(rest: &str) -> Result<(), &str> {
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {Ok(())} else {Err(rest)}
+}
+
+/// Reads a string up to its closing quote, returning it and what follows.
+fn unquote(string: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = string.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &string[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                escaped => value.push(escaped)
+            },
+            c => value.push(c)
+        }
+    }
+    None
+}
+
+/// `value` as a quoted string that [`parse`] reads back unchanged.
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\t' => quoted += "\\t",
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let sections = parse("# answers\nargs = \"100\"\n\n[day5.abc] # first\npart1 = 35 # bare\npart2 = \"a # b\"\n").unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].header, None);
+        assert_eq!(sections[0].entries[0].key.name, "args");
+        assert_eq!(sections[1].header.map(|header| header.name), Some("day5.abc"));
+        let values: Vec<(&str, &str, bool)> = sections[1].entries.iter()
+            .map(|entry| (entry.key.name, entry.value.as_str(), entry.quoted))
+            .collect();
+        assert_eq!(values, vec![("part1", "35", false), ("part2", "a # b", true)]);
+        assert_eq!(parse("").unwrap(), vec![Section { header: None, entries: Vec::new() }]);
+    }
+
+    #[test]
+    fn test_quote() {
+        for value in ["plain", "tab\tand \"quotes\"", "back\\slash # and\nnewline"] {
+            let line = format!("key = {}", quote(value));
+            let sections = parse(&line).unwrap();
+            assert_eq!(sections[0].entries[0].value, value);
+        }
+        assert_eq!(quote("say \"hi\""), r#""say \"hi\"""#);
+    }
+
+    #[test]
+    fn test_errors() {
+        let message = |contents: &str| parse(contents).unwrap_err().to_string();
+        assert!(message("key").contains("Expected 'key = value'."));
+        assert!(message("= 1").contains("Expected a key"));
+        assert!(message("[section").contains("Expected a closing ']'."));
+        let error = message("a = 1\nkey = \"open");
+        assert!(error.contains("Expected a closing '\"'."));
+        assert!(error.contains(":2:7"));
+        assert!(message("key = \"value\" extra").contains("Expected a comment or the end of the line."));
+
+        let sections = parse("\n  part3 = 1").unwrap();
+        let entry = &sections[0].entries[0];
+        assert!(entry.key.error("Unknown key").to_string().contains(":2:3"));
+        assert!(entry.value_error("Not quoted").to_string().contains(":2:11"));
+    }
+}
@@ -0,0 +1,281 @@
+//! Draws grids in the terminal one frame at a time, for days that show how
+//! they get to their answers with `--visualize`.
+
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::errors::AOCError;
+use crate::grid::{Grid, Position};
+
+pub const DEFAULT_FPS: f64 = 10.0;
+
+/// The terminal colours a cell can be drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::Gray => 90
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Option<Color>,
+    /// Drawn in reverse video, so it stands out even without colours.
+    pub highlighted: bool
+}
+
+/// One picture of a grid with a caption below it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub cells: Grid<Cell>,
+    pub caption: String
+}
+
+impl Frame {
+    pub fn new(grid: &Grid<char>) -> Self {
+        let cells = grid.map(|symbol| Cell { symbol: *symbol, color: None, highlighted: false });
+        Self { cells, caption: String::new() }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    pub fn set(&mut self, position: impl Into<Position>, symbol: char) {
+        self.cells[position].symbol = symbol;
+    }
+
+    pub fn color(&mut self, position: impl Into<Position>, color: Color) {
+        self.cells[position].color = Some(color);
+    }
+
+    pub fn highlight(&mut self, position: impl Into<Position>, color: Color) {
+        let cell = &mut self.cells[position];
+        cell.color = Some(color);
+        cell.highlighted = true;
+    }
+
+    /// The grid and caption as text, with ANSI colours if `colors` is set.
+    /// Without colours highlighted cells are drawn as `#`.
+    pub fn render(&self, colors: bool) -> String {
+        let mut text = String::new();
+        for row in self.cells.rows() {
+            let mut current: Option<(Option<Color>, bool)> = None;
+            for cell in row {
+                if !colors {
+                    text.push(if cell.highlighted { '#' } else { cell.symbol });
+                    continue;
+                }
+                let style = (cell.color, cell.highlighted);
+                if current != Some(style) {
+                    text += "\x1b[0m";
+                    if let Some(color) = cell.color {
+                        text += &format!("\x1b[{}m", color.code());
+                    }
+                    if cell.highlighted {
+                        text += "\x1b[7m";
+                    }
+                    current = Some(style);
+                }
+                text.push(cell.symbol);
+            }
+            if colors {
+                text += "\x1b[0m";
+            }
+            text.push('\n');
+        }
+        if !self.caption.is_empty() {
+            text += &self.caption;
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// How frames are shown: at a steady rate, or one by one on Enter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Playback {
+    pub fps: f64,
+    pub step: bool
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self { fps: DEFAULT_FPS, step: false }
+    }
+}
+
+/// Shows frames one after the other, see [`Playback`].
+///
+/// In step mode Enter shows the next frame, `c` plays the rest at the frame
+/// rate and `q` stops, after which frames are no longer shown.
+pub struct Player {
+    playback: Playback,
+    colors: bool,
+    output: Output,
+    shown: usize,
+    stopped: bool
+}
+
+enum Output {
+    Terminal {
+        out: Box<dyn Write>,
+        keys: Box<dyn BufRead>
+    },
+    Recording(Vec<Frame>)
+}
+
+impl Player {
+    /// Draws to stdout and reads keys from stdin. Colours are left out when
+    /// stdout isn't a terminal or `NO_COLOR` is set.
+    pub fn terminal(playback: Playback) -> Self {
+        let colors = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+        Self::new(Box::new(io::stdout()), Box::new(io::stdin().lock()), playback, colors)
+    }
+
+    pub fn new(out: Box<dyn Write>, keys: Box<dyn BufRead>, playback: Playback, colors: bool) -> Self {
+        Self { playback, colors, output: Output::Terminal { out, keys }, shown: 0, stopped: false }
+    }
+
+    /// Keeps the frames instead of drawing them, for tests.
+    pub fn recording() -> Self {
+        Self { playback: Playback::default(), colors: false, output: Output::Recording(Vec::new()), shown: 0, stopped: false }
+    }
+
+    /// The recorded frames, empty unless made with [`Player::recording`].
+    pub fn frames(&self) -> &[Frame] {
+        match &self.output {
+            Output::Recording(frames) => frames,
+            Output::Terminal { .. } => &[]
+        }
+    }
+
+    /// How many frames were shown so far.
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+
+    /// Whether `q` was pressed. Long animations should check this and stop early.
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    pub fn show(&mut self, frame: &Frame) -> Result<(), AOCError> {
+        if self.stopped {
+            return Ok(());
+        }
+        self.shown += 1;
+        let (out, keys) = match &mut self.output {
+            Output::Recording(frames) => {
+                frames.push(frame.clone());
+                return Ok(());
+            },
+            Output::Terminal { out, keys } => (out, keys)
+        };
+
+        if self.colors {
+            // Back to the top left corner and clear the screen.
+            write!(out, "\x1b[H\x1b[2J")?;
+        }
+        write!(out, "{}", frame.render(self.colors))?;
+        if !self.playback.step {
+            out.flush()?;
+            thread::sleep(Duration::from_secs_f64(1.0 / self.playback.fps));
+            return Ok(());
+        }
+
+        write!(out, "[frame {}] Enter: next, c: continue, q: quit ", self.shown)?;
+        out.flush()?;
+        let mut key = String::new();
+        match keys.read_line(&mut key)? {
+            // Nothing left to read, play the rest.
+            0 => self.playback.step = false,
+            _ => match key.trim() {
+                "c" => self.playback.step = false,
+                "q" => self.stopped = true,
+                _ => ()
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(&"ab\ncd".parse().unwrap()).with_caption("two by two");
+        frame.color((1, 0), Color::Green);
+        frame.highlight((0, 1), Color::Red);
+        frame
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(frame().render(false), "ab\n#d\ntwo by two\n");
+        assert_eq!(
+            frame().render(true),
+            "\x1b[0ma\x1b[0m\x1b[32mb\x1b[0m\n\x1b[0m\x1b[31m\x1b[7mc\x1b[0md\x1b[0m\ntwo by two\n"
+        );
+    }
+
+    #[test]
+    fn test_frame_set() {
+        let mut frame = frame();
+        frame.set((1, 1), '*');
+        assert_eq!(frame.cells[(1, 1)], Cell { symbol: '*', color: None, highlighted: false });
+        assert_eq!(frame.cells[(0, 1)], Cell { symbol: 'c', color: Some(Color::Red), highlighted: true });
+    }
+
+    #[test]
+    fn test_recording() {
+        let mut player = Player::recording();
+        player.show(&frame()).unwrap();
+        player.show(&Frame::new(&"x".parse().unwrap())).unwrap();
+        assert_eq!(player.shown(), 2);
+        assert_eq!(player.frames()[0], frame());
+    }
+
+    #[test]
+    fn test_step_keys() {
+        let step = Playback { fps: 1000.0, step: true };
+        let mut player = Player::new(Box::new(io::sink()), Box::new(Cursor::new("\nq\n")), step, false);
+        for _ in 0..4 {
+            player.show(&frame()).unwrap();
+        }
+        assert!(player.stopped());
+        assert_eq!(player.shown(), 2);
+
+        let mut player = Player::new(Box::new(io::sink()), Box::new(Cursor::new("c\n")), step, false);
+        for _ in 0..3 {
+            player.show(&frame()).unwrap();
+        }
+        assert!(!player.stopped());
+        assert_eq!(player.shown(), 3);
+    }
+}
@@ -0,0 +1,141 @@
+//! Day 1: Trebuchet?! Every line of the calibration document hides a value
+//! made of its first and last digit.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use aoc::errors::AOCError;
+use aoc::Solution;
+
+static RADIX: u32 = 10;
+
+fn number_lookup() -> &'static HashMap<&'static str, u32> {
+    static HASHMAP: OnceLock<HashMap<&str, u32>> = OnceLock::new();
+    HASHMAP.get_or_init(|| {
+        let mut map: HashMap<&str, u32> = HashMap::new();
+        map.insert("one", 1);
+        map.insert("two", 2);
+        map.insert("three", 3);
+        map.insert("four", 4);
+        map.insert("five", 5);
+        map.insert("six", 6);
+        map.insert("seven", 7);
+        map.insert("eight", 8);
+        map.insert("nine", 9);
+        map
+    })
+}
+
+#[derive(Default)]
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Self::Input) -> Result<u32, AOCError> {
+        sum_of_calibration_values(lines, false)
+    }
+
+    fn part2(&self, lines: &Self::Input) -> Result<u32, AOCError> {
+        sum_of_calibration_values(lines, true)
+    }
+}
+
+/// The sum of the calibration values of all lines, see [`extract_number`].
+pub fn sum_of_calibration_values(lines: &[String], with_words: bool) -> Result<u32, AOCError> {
+    let mut sum: u32 = 0;
+    for (i, line) in lines.iter().enumerate() {
+        sum += extract_number(line, with_words)
+            .map_err(|error| error.at_line(i + 1, line))?;
+    }
+    Ok(sum)
+}
+
+/// The first and last digit of `line` as a two digit number. Digits spelled
+/// out as words count too `with_words`, even when they overlap like `eightwo`.
+pub fn extract_number(line: &str, with_words: bool) -> Result<u32, AOCError> {
+    let numbers = find_numbers(line, with_words);
+    match (numbers.first(), numbers.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(AOCError::parse(if with_words {
+            "Expected at least one digit or spelled out number."
+        } else {
+            "Expected at least one digit."
+        }))
+    }
+}
+
+fn find_numbers(line: &str, with_words: bool) -> Vec<u32> {
+    let mut numbers: Vec<u32> = Vec::new();
+    for (i, c) in line.char_indices() {
+        if let Some(digit) = c.to_digit(RADIX) {
+            numbers.push(digit);
+            continue;
+        }
+        if !with_words {
+            continue;
+        }
+        for length in 3..6 {
+            let end = i + c.len_utf8();
+            if end >= length {
+                if let Some(value) = check_substring(line, end, length) {
+                    numbers.push(value);
+                    break;
+                }
+            }
+        }
+    }
+    numbers
+}
+
+fn check_substring(line: &str, end: usize, length: usize) -> Option<u32> {
+    let substring = line.get((end - length)..end)?;
+    number_lookup().get(substring).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use aoc::get_input_buffer;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_number2() {
+        assert_eq!(extract_number("two1nine", true).unwrap(), 29);
+        assert_eq!(extract_number("ñeightwö", true).unwrap(), 88);
+    }
+
+    #[test]
+    fn test_line_without_digits() {
+        let lines = Day1.parse("1abc2\nabcdef\n").unwrap();
+        let message = Day1.part1(&lines).unwrap_err().to_string();
+        assert!(message.contains("Expected at least one digit."));
+        assert!(message.contains("2 | abcdef"));
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let file_contains_expected_line = get_input_buffer(PathBuf::from("./tests/input.txt")).unwrap()
+            .lines()
+            .any(|line| line == "a1b2c3d4e5f");
+        assert!(file_contains_expected_line);
+    }
+
+    #[test]
+    fn test_extract_number() {
+        assert_eq!(extract_number("1abc2", false).unwrap(), 12);
+        assert_eq!(extract_number("pqr3stu8vwx", false).unwrap(), 38);
+        assert_eq!(extract_number("a1b2c3d4e5f", false).unwrap(), 15);
+        assert_eq!(extract_number("treb7uchet", false).unwrap(), 77);
+    }
+}
@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day1::{extract_number, sum_of_calibration_values, Day1};
+
+fn example(name: &str) -> Vec<String> {
+    Day1.parse_input(Path::new("tests").join(name)).unwrap()
+}
+
+#[test]
+fn test_sum_of_calibration_values() {
+    assert_eq!(sum_of_calibration_values(&example("input.txt"), false).unwrap(), 142);
+    assert_eq!(sum_of_calibration_values(&example("input2.txt"), true).unwrap(), 281);
+}
+
+#[test]
+fn test_extract_number() {
+    assert_eq!(extract_number("treb7uchet", false).unwrap(), 77);
+    assert_eq!(extract_number("eightwothree", true).unwrap(), 83);
+    assert!(extract_number("eightwothree", false).is_err());
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day1::Day1);
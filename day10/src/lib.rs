@@ -0,0 +1,315 @@
+//! Day 10: Pipe Maze. Follows the loop of pipes through the start and
+//! counts the tiles it encloses.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc::errors::AOCError;
+use aoc::geometry::{interior_points, Direction, UPoint};
+use aoc::geometry::Direction::{East, North, South, West};
+use aoc::graph::{Graph, NodeId};
+use aoc::grid::Grid;
+use aoc::viz::{Color, Frame, Player};
+use aoc::Solution;
+
+#[derive(Default)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = PipeMaze;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.parse()
+    }
+
+    fn part1(&self, maze: &Self::Input) -> Result<u64, AOCError> {
+        farthest_distance(maze)
+    }
+
+    fn part2(&self, maze: &Self::Input) -> Result<u64, AOCError> {
+        enclosed_tiles(maze)
+    }
+
+    fn visualize(&self, maze: &Self::Input, player: &mut Player) -> Result<(), AOCError> {
+        trace_loop(maze, player)
+    }
+}
+
+/// The tiles of the maze, where the start is and which tiles every pipe connects.
+#[derive(Debug)]
+pub struct PipeMaze {
+    pub grid: Grid<char>,
+    pub start: UPoint,
+    /// Every tile with a pipe points at the up to two tiles it connects to.
+    pub pipes: Graph<UPoint>,
+}
+
+impl FromStr for PipeMaze {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse(s, |c| match c {
+            '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' | 'S' => Ok(c),
+            _ => Err(AOCError::parse(format!("'{c}' is not a pipe, expected one of |-LJ7F.S")))
+        })?;
+        let start = find_start(&grid)?;
+        let pipes = pipe_graph(&grid, start)?;
+        Ok(Self { grid, start, pipes })
+    }
+}
+
+/// How many steps along the loop the tile farthest from the start is.
+pub fn farthest_distance(maze: &PipeMaze) -> Result<u64, AOCError> {
+    let path = find_loop(maze)?;
+    Ok(path.len() as u64 / 2)
+}
+
+/// The tiles on the loop are the lattice points on the edges of a polygon,
+/// so Pick's theorem gives the tiles enclosed by it.
+pub fn enclosed_tiles(maze: &PipeMaze) -> Result<u64, AOCError> {
+    let path = find_loop(maze)?;
+    Ok(interior_points(&path))
+}
+
+/// The two directions a pipe connects, `None` for ground and the start.
+fn connections(pipe: char) -> Option<[Direction; 2]> {
+    match pipe {
+        '|' => Some([North, South]), // is a vertical pipe connecting north and south
+        '-' => Some([East, West]), // is a horizontal pipe connecting east and west
+        'L' => Some([North, East]), // is a 90-degree bend connecting north and east
+        'J' => Some([North, West]), // is a 90-degree bend connecting north and west
+        '7' => Some([South, West]), // is a 90-degree bend connecting south and west
+        'F' => Some([South, East]), // is a 90-degree bend connecting south and east
+        _ => None // is ground or the starting position of the animal
+    }
+}
+
+fn find_start(grid: &Grid<char>) -> Result<UPoint, AOCError> {
+    let mut starts = grid.find_all(|c| *c == 'S');
+    let start = starts.next().ok_or(AOCError::parse("Expected a start 'S' in the maze."))?;
+    if let Some((x, y)) = starts.next() {
+        let line: String = grid.row(y).iter().collect();
+        return Err(AOCError::parse("Expected only one start 'S'.")
+            .at_column(x + 1)
+            .at_line(y + 1, &line));
+    }
+    Ok(start.into())
+}
+
+/// The directions from the start whose neighbouring pipes connect back to it.
+fn starting_directions(grid: &Grid<char>, start: UPoint) -> Result<[Direction; 2], AOCError> {
+    let directions: Vec<Direction> = Direction::CARDINAL.into_iter()
+        .filter(|direction| {
+            start.step(*direction)
+                .and_then(|position| grid.get(position))
+                .and_then(|pipe| connections(*pipe))
+                .is_some_and(|connections| connections.contains(&direction.opposite()))
+        })
+        .collect();
+    match directions[..] {
+        [first, second, ..] => Ok([first, second]),
+        _ => Err(AOCError::no_solution(format!("fewer than two pipes connect to the start at {start}")))
+    }
+}
+
+/// The start points at the neighbours connecting back to it.
+fn pipe_graph(grid: &Grid<char>, start: UPoint) -> Result<Graph<UPoint>, AOCError> {
+    let mut graph: Graph<UPoint> = Graph::new();
+    for (position, c) in grid.iter() {
+        let position = UPoint::from(position);
+        let directions = match connections(*c) {
+            Some(directions) => directions,
+            None if position == start => starting_directions(grid, start)?,
+            None => continue
+        };
+        let from = graph.intern(position);
+        for next in directions.into_iter().filter_map(|direction| position.step(direction)) {
+            if grid.contains(next) {
+                let to = graph.intern(next);
+                graph.add_edge(from, to, ());
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Follows the loop from the start back to it, returning every tile on it in order.
+pub fn find_loop(maze: &PipeMaze) -> Result<Vec<UPoint>, AOCError> {
+    let PipeMaze { grid, start, pipes: graph } = maze;
+    let start = *start;
+    let start_id = graph.id(&start).ok_or(AOCError::no_solution("the start is not on the loop"))?;
+
+    let mut path: Vec<UPoint> = vec![start];
+    let mut previous = start_id;
+    let mut current = graph.successors(start_id).next()
+        .ok_or(AOCError::no_solution(format!("the loop leaves the maze at {start}")))?;
+    while current != start_id {
+        let position = *graph.node(current);
+        path.push(position);
+        let successors: Vec<NodeId> = graph.successors(current).collect();
+        if !successors.contains(&previous) {
+            return Err(AOCError::no_solution(match grid[position] {
+                '.' => format!("the loop is broken at {position}"),
+                pipe => format!("'{pipe}' at {position} doesn't connect back")
+            }));
+        }
+        let next = successors.into_iter().find(|next| *next != previous)
+            .ok_or(AOCError::no_solution(format!("the loop leaves the maze at {position}")))?;
+        previous = current;
+        current = next;
+    }
+    Ok(path)
+}
+
+/// Traces the loop from the start in about a hundred frames, then fills
+/// the tiles it encloses.
+pub fn trace_loop(maze: &PipeMaze, player: &mut Player) -> Result<(), AOCError> {
+    let path = find_loop(maze)?;
+    let mut frame = Frame::new(&maze.grid.map(|c| box_drawing(*c)));
+    for (position, _) in maze.grid.iter() {
+        frame.color(position, Color::Gray);
+    }
+    frame.highlight(maze.start, Color::Yellow);
+
+    let mut traced = 0;
+    for steps in path[1..].chunks(path.len().div_ceil(100)) {
+        for position in steps {
+            frame.color(*position, Color::Green);
+        }
+        traced += steps.len();
+        let mut current = frame.clone()
+            .with_caption(format!("{traced} of {} steps around the loop", path.len()));
+        current.highlight(steps[steps.len() - 1], Color::Red);
+        player.show(&current)?;
+        if player.stopped() {
+            return Ok(());
+        }
+    }
+
+    let inside = inside_tiles(maze, &path)?;
+    for position in &inside {
+        frame.highlight(*position, Color::Blue);
+    }
+    let frame = frame.with_caption(format!(
+        "The farthest tile is {} steps away, the loop encloses {} tiles",
+        path.len() / 2,
+        inside.len()
+    ));
+    player.show(&frame)
+}
+
+/// The tiles not on the loop with an odd number of loop pipes going north
+/// to their left.
+fn inside_tiles(maze: &PipeMaze, path: &[UPoint]) -> Result<Vec<UPoint>, AOCError> {
+    let on_loop: HashSet<UPoint> = path.iter().copied().collect();
+    let start = starting_directions(&maze.grid, maze.start)?;
+    let mut inside = Vec::new();
+    for y in 0..maze.grid.height() {
+        let mut crossed = false;
+        for x in 0..maze.grid.width() {
+            let position = UPoint::new(x, y);
+            if !on_loop.contains(&position) {
+                if crossed {
+                    inside.push(position);
+                }
+                continue;
+            }
+            let directions = connections(maze.grid[position]).unwrap_or(start);
+            if directions.contains(&North) {
+                crossed = !crossed;
+            }
+        }
+    }
+    Ok(inside)
+}
+
+fn box_drawing(pipe: char) -> char {
+    match pipe {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        other => other
+    }
+}
+
+/// Draws the maze's pipes in Graphviz DOT with the loop through the start filled.
+pub fn to_dot(maze: &PipeMaze) -> Result<String, AOCError> {
+    let path: Vec<NodeId> = find_loop(maze)?.iter()
+        .filter_map(|position| maze.pipes.id(position))
+        .collect();
+    Ok(maze.pipes.to_dot(&path, |_| None))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc::read_input;
+
+    use super::*;
+
+    fn input(path: &str) -> PipeMaze {
+        Day10.parse(&read_input(Path::new(path)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_malformed_maze() {
+        let message = |input: &str| Day10.parse(input).and_then(|maze| Day10.part1(&maze)).unwrap_err().to_string();
+        let error = message(".....\n.S-7.\n.|x|.");
+        assert!(error.contains("'x' is not a pipe"));
+        assert!(error.contains(":3:3"));
+        assert!(message("..\n..").contains("Expected a start 'S'"));
+        assert!(message("S-7\n|.|\nL-S").contains("Expected only one start"));
+        assert!(message("S..\n...").contains("fewer than two pipes"));
+    }
+
+    #[test]
+    fn test_broken_loops() {
+        let message = |input: &str| Day10.parse(input).and_then(|maze| Day10.part2(&maze)).unwrap_err().to_string();
+        assert!(message("S--\n|..\nL--").contains("leaves the maze"));
+        assert!(message("S-7\n|.|\nL.J").contains("the loop is broken"));
+        assert!(message("S-7\n|.|\nL|J").contains("doesn't connect back"));
+    }
+
+    #[test]
+    fn test_find_loop() {
+        let maze = input("tests/example1.txt");
+        assert_eq!(maze.start, UPoint::new(1, 1));
+        assert_eq!(starting_directions(&maze.grid, UPoint::new(1, 1)).unwrap(), [East, South]);
+        let path = find_loop(&maze).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[1], UPoint::new(2, 1));
+    }
+
+    #[test]
+    fn test_trace_loop() {
+        for (name, enclosed) in [("tests/example2.txt", 1), ("tests/part2_1.txt", 4), ("tests/part2_2.txt", 8)] {
+            let maze = input(name);
+            let mut player = Player::recording();
+            trace_loop(&maze, &mut player).unwrap();
+            let last = player.frames().last().unwrap();
+            assert!(last.caption.ends_with(&format!("encloses {enclosed} tiles")), "{name}: {}", last.caption);
+            assert_eq!(inside_tiles(&maze, &find_loop(&maze).unwrap()).unwrap().len() as u64, enclosed_tiles(&maze).unwrap());
+        }
+        let mut player = Player::recording();
+        trace_loop(&input("tests/example1.txt"), &mut player).unwrap();
+        assert_eq!(player.frames().len(), 8);
+        assert_eq!(player.frames()[0].caption, "1 of 8 steps around the loop");
+        assert_eq!(player.frames()[0].cells[(2, 1)].symbol, '─');
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&input("tests/example1.txt")).unwrap();
+        assert!(dot.contains("[label=\"(1, 1)\", style=filled, fillcolor=gold];"));
+        assert_eq!(dot.matches("fillcolor").count(), 8);
+        assert_eq!(dot.matches(" -> ").count(), 16);
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use aoc::geometry::UPoint;
+use aoc::Solution;
+use day10::{enclosed_tiles, farthest_distance, find_loop, Day10, PipeMaze};
+
+fn example(name: &str) -> PipeMaze {
+    Day10.parse_input(Path::new("tests").join(name)).unwrap()
+}
+
+#[test]
+fn test_find_loop() {
+    let maze = example("example1.txt");
+    assert_eq!(maze.start, UPoint::new(1, 1));
+    assert_eq!(maze.grid[(2, 1)], '-');
+    let path = find_loop(&maze).unwrap();
+    assert_eq!(path.len(), 8);
+    assert_eq!(path[0], UPoint::new(1, 1));
+}
+
+#[test]
+fn test_part_functions() {
+    assert_eq!(farthest_distance(&example("example2.txt")).unwrap(), 8);
+    assert_eq!(enclosed_tiles(&example("part2_1.txt")).unwrap(), 4);
+    assert_eq!(enclosed_tiles(&example("part2_2.txt")).unwrap(), 8);
+}
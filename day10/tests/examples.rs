@@ -0,0 +1 @@
+aoc::example_tests!(day10::Day10);
@@ -0,0 +1,195 @@
+//! Day 11: Cosmic Expansion. Sums the distances between every pair of
+//! galaxies after the empty rows and columns of the image grew.
+
+use std::str::FromStr;
+
+use aoc::Solution;
+use aoc::errors::AOCError;
+use aoc::geometry::UPoint;
+use aoc::grid::Grid;
+use aoc::viz::{Color, Frame, Player};
+
+#[derive(Debug)]
+pub struct Day11 {
+    /// How many rows or columns an empty one becomes in part 2.
+    expansion: usize,
+}
+
+impl Default for Day11 {
+    fn default() -> Self {
+        Self { expansion: 1_000_000 }
+    }
+}
+
+impl Day11 {
+    /// Every empty row or column becomes `expansion` of them in part 2.
+    pub fn new(expansion: usize) -> Self {
+        Self { expansion }
+    }
+}
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const ARGS: &'static str = "[EXPANSION]";
+
+    type Input = Universe;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn with_args(args: &[String]) -> Result<Self, AOCError> {
+        match args {
+            [] => Ok(Self::default()),
+            [expansion] => expansion.parse().ok()
+                .filter(|expansion| *expansion > 0)
+                .map(|expansion| Self { expansion })
+                .ok_or(AOCError::invalid_argument(format!("'{expansion}' is not a valid expansion."))),
+            _ => Err(AOCError::invalid_argument(format!("Expected the EXPANSION of empty space, got {} arguments", args.len())))
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.parse()
+    }
+
+    fn part1(&self, universe: &Self::Input) -> Result<usize, AOCError> {
+        Ok(galaxy_distances(universe, 2))
+    }
+
+    fn part2(&self, universe: &Self::Input) -> Result<usize, AOCError> {
+        Ok(galaxy_distances(universe, self.expansion))
+    }
+
+    fn visualize(&self, universe: &Self::Input, player: &mut Player) -> Result<(), AOCError> {
+        show_expansion(universe, self.expansion, player)
+    }
+}
+
+/// The galaxies in the image and which of its rows and columns are empty.
+#[derive(Debug)]
+pub struct Universe {
+    pub galaxies: Vec<UPoint>,
+    pub empty_rows: Vec<bool>,
+    pub empty_columns: Vec<bool>,
+}
+
+impl Universe {
+    /// Where the galaxies are after every empty row or column grew to `expanse` of them.
+    pub fn expanded(&self, expanse: usize) -> Vec<UPoint> {
+        let xs = coordinates(&self.empty_columns, expanse);
+        let ys = coordinates(&self.empty_rows, expanse);
+        self.galaxies.iter().map(|galaxy| UPoint::new(xs[galaxy.x], ys[galaxy.y])).collect()
+    }
+}
+
+impl FromStr for Universe {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let image = Grid::parse(s, |c| match c {
+            '.' | '#' => Ok(c),
+            _ => Err(AOCError::parse(format!("'{c}' is neither empty space '.' nor a galaxy '#'.")))
+        })?;
+        Ok(Self {
+            galaxies: image.find_all(|c| *c == '#').map(UPoint::from).collect(),
+            empty_rows: image.rows().map(|row| row.iter().all(|c| *c == '.')).collect(),
+            empty_columns: image.columns().map(|mut column| column.all(|c| *c == '.')).collect()
+        })
+    }
+}
+
+/// Shows the image with its empty rows and columns, then the image after
+/// they doubled. The distances with `expansion` are only given as a number.
+pub fn show_expansion(universe: &Universe, expansion: usize, player: &mut Player) -> Result<(), AOCError> {
+    let image = |empty_rows: &[bool], empty_columns: &[bool], galaxies: &[UPoint]| {
+        let mut frame = Frame::new(&Grid::new(empty_columns.len(), empty_rows.len(), '.'));
+        for (y, empty_row) in empty_rows.iter().enumerate() {
+            for (x, empty_column) in empty_columns.iter().enumerate() {
+                frame.color((x, y), if *empty_row || *empty_column { Color::Blue } else { Color::Gray });
+            }
+        }
+        for galaxy in galaxies {
+            frame.set(*galaxy, '#');
+            frame.highlight(*galaxy, Color::Yellow);
+        }
+        frame
+    };
+    let empty = |lines: &[bool]| lines.iter().filter(|empty| **empty).count();
+
+    player.show(&image(&universe.empty_rows, &universe.empty_columns, &universe.galaxies).with_caption(format!(
+        "{} galaxies, {} empty rows and {} empty columns",
+        universe.galaxies.len(),
+        empty(&universe.empty_rows),
+        empty(&universe.empty_columns)
+    )))?;
+
+    let doubled = |lines: &[bool]| lines.iter().flat_map(|empty| vec![*empty; if *empty {2} else {1}]).collect::<Vec<bool>>();
+    let frame = image(&doubled(&universe.empty_rows), &doubled(&universe.empty_columns), &universe.expanded(2))
+        .with_caption(format!(
+            "The distances sum to {} after doubling, {} after growing {expansion} times",
+            galaxy_distances(universe, 2),
+            galaxy_distances(universe, expansion)
+        ));
+    player.show(&frame)
+}
+
+/// Maps every row or column to its coordinate after empty ones grew by `expanse`.
+fn coordinates(empty: &[bool], expanse: usize) -> Vec<usize> {
+    let mut coordinate = 0;
+    empty.iter().map(|empty| {
+        let current = coordinate;
+        coordinate += if *empty {expanse} else {1};
+        current
+    }).collect()
+}
+
+/// The sum of the shortest paths between every pair of galaxies, with every
+/// empty row or column counting as `expanse`.
+pub fn galaxy_distances(universe: &Universe, expanse: usize) -> usize {
+    let galaxies = universe.expanded(expanse);
+
+    let mut distance: usize = 0;
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[i + 1..] {
+            distance += a.manhattan(*b);
+        }
+    }
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_image() {
+        let error = Day11::default().parse("...#\n.#*.").unwrap_err().to_string();
+        assert!(error.contains("'*' is neither"));
+        assert!(error.contains(":2:3"));
+    }
+
+    #[test]
+    fn test_expanded() {
+        let universe = Day11::default().parse("#..\n...\n..#").unwrap();
+        assert_eq!(universe.empty_rows, vec![false, true, false]);
+        assert_eq!(universe.expanded(10), vec![UPoint::new(0, 0), UPoint::new(11, 11)]);
+        assert_eq!(coordinates(&[false, true, true, false], 3), vec![0, 1, 4, 7]);
+    }
+
+    #[test]
+    fn test_show_expansion() {
+        let universe = Day11::default().parse("#..\n...\n..#").unwrap();
+        let mut player = Player::recording();
+        show_expansion(&universe, 10, &mut player).unwrap();
+        let frames = player.frames();
+        assert_eq!(frames[0].caption, "2 galaxies, 1 empty rows and 1 empty columns");
+        assert_eq!(frames[1].render(false), "#...\n....\n....\n...#\nThe distances sum to 6 after doubling, 22 after growing 10 times\n");
+        assert_eq!(frames[1].cells[(1, 0)].color, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_with_args() {
+        assert_eq!(Day11::with_args(&[String::from("100")]).unwrap().expansion, 100);
+        assert_eq!(Day11::with_args(&[]).unwrap().expansion, 1_000_000);
+        assert!(Day11::with_args(&[String::from("0")]).unwrap_err().to_string().contains("'0'"));
+    }
+}
@@ -0,0 +1,13 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day11::{galaxy_distances, Day11};
+
+#[test]
+fn test_galaxy_distances() {
+    let universe = Day11::default().parse_input(Path::new("tests/input.txt")).unwrap();
+    assert_eq!(universe.galaxies.len(), 9);
+    assert_eq!(galaxy_distances(&universe, 2), 374);
+    assert_eq!(galaxy_distances(&universe, 10), 1030);
+    assert_eq!(Day11::new(100).part2(&universe).unwrap(), 8410);
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day11::Day11);
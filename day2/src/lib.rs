@@ -0,0 +1,258 @@
+//! Day 2: Cube Conundrum. Games of cubes drawn from a bag, which are only
+//! possible if the bag holds enough cubes of every colour.
+
+use std::cmp::max;
+use std::str::FromStr;
+
+use aoc::errors::AOCError;
+use aoc::parse::{parse_line, parse_lines, Cursor, Parsed};
+use aoc::Solution;
+
+/// A number of cubes of each colour, drawn from or held in the bag.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Cubes {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl Cubes {
+    pub fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+
+    /// Reads a draw like `3 blue, 4 red`, colours not mentioned count 0.
+    fn parse(cursor: &mut Cursor) -> Parsed<Self> {
+        let mut cubes = Cubes { red: 0, green: 0, blue: 0 };
+        for (count, color, position) in cursor.separated(",", |cursor| {
+            let count: u32 = cursor.unsigned()?;
+            let position = *cursor;
+            Ok((count, cursor.word()?, position))
+        })? {
+            match color {
+                "red" => cubes.red = count,
+                "green" => cubes.green = count,
+                "blue" => cubes.blue = count,
+                _ => return Err(position.error(format!("'{color}' is not a valid cube color.")))
+            }
+        }
+        Ok(cubes)
+    }
+}
+
+impl FromStr for Cubes {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_line(s, Cubes::parse)
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Cubes>,
+}
+
+impl Game {
+    /// The fewest cubes of each colour the bag needs for this game.
+    pub fn required(&self) -> Cubes {
+        let mut required = Cubes { red: 0, green: 0, blue: 0 };
+        for draw in &self.draws {
+            required.red = max(required.red, draw.red);
+            required.green = max(required.green, draw.green);
+            required.blue = max(required.blue, draw.blue);
+        }
+        required
+    }
+
+    pub fn possible(&self, bag: &Cubes) -> bool {
+        for draw in &self.draws {
+            if draw.red > bag.red || draw.green > bag.green || draw.blue > bag.blue {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Reads a game like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    fn parse(cursor: &mut Cursor) -> Parsed<Self> {
+        let id = game_id(cursor)?;
+        cursor.literal(":")?;
+        let draws = cursor.separated(";", Cubes::parse)?;
+        Ok(Game { id, draws })
+    }
+}
+
+impl FromStr for Game {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_line(s, Game::parse)
+    }
+}
+
+#[derive(Debug)]
+pub struct Day2 {
+    bag: Cubes,
+}
+
+impl Default for Day2 {
+    fn default() -> Self {
+        Self { bag: Cubes { red: 12, green: 13, blue: 14 } }
+    }
+}
+
+impl Day2 {
+    /// Checks the games against `bag` instead of the 12 red, 13 green and 14 blue cubes of the puzzle.
+    pub fn new(bag: Cubes) -> Self {
+        Self { bag }
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    const ARGS: &'static str = "[RED GREEN BLUE]";
+
+    type Input = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn with_args(args: &[String]) -> Result<Self, AOCError> {
+        match args {
+            [] => Ok(Self::default()),
+            [red, green, blue] => Ok(Self { bag: Cubes {
+                red: parse_bag_size(red, "red")?,
+                green: parse_bag_size(green, "green")?,
+                blue: parse_bag_size(blue, "blue")?
+            }}),
+            _ => Err(AOCError::invalid_argument(format!("Expected the RED GREEN BLUE bag sizes, got {} arguments", args.len())))
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        parse_lines(input, Game::parse)
+    }
+
+    fn part1(&self, games: &Self::Input) -> Result<u32, AOCError> {
+        Ok(sum_of_possible_games(games, &self.bag))
+    }
+
+    fn part2(&self, games: &Self::Input) -> Result<u32, AOCError> {
+        Ok(sum_of_minimum_power(games))
+    }
+}
+
+fn parse_bag_size(value: &str, color: &str) -> Result<u32, AOCError> {
+    value.parse::<u32>()
+        .map_err(|_| AOCError::invalid_argument(format!("'{value}' is not a valid number of {color} cubes.")))
+}
+
+pub fn sum_of_minimum_power(games: &[Game]) -> u32 {
+    games.iter().map(|game| game.required().power()).sum()
+}
+
+/// The sum of the ids of the games which are possible with `bag`.
+pub fn sum_of_possible_games(games: &[Game], bag: &Cubes) -> u32 {
+    games.iter()
+        .filter(|game| game.possible(bag))
+        .map(|game| game.id)
+        .sum()
+}
+
+fn game_id(cursor: &mut Cursor) -> Parsed<u32> {
+    cursor.labelled("Game", Cursor::unsigned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cubes_power() {
+        let cubes = Cubes { red: 2, green: 3, blue: 4 };
+        assert_eq!(cubes.power(), 24);
+    }
+
+    #[test]
+    fn test_game_required() {
+        let game = Game { id: 1, draws: vec![
+            Cubes { red: 1, green: 5, blue: 4 },
+            Cubes { red: 2, green: 3, blue: 4 }
+        ]};
+        assert_eq!(game.required(), Cubes { red: 2, green: 5, blue: 4 });
+    }
+
+    #[test]
+    fn test_game_id() {
+        let game_id = parse_line("Game 42", game_id).unwrap();
+        assert_eq!(game_id, 42);
+    }
+
+    #[test]
+    fn test_cubes_from_str() {
+        let cubes = Cubes::from_str("8 green, 6 blue, 20 red").unwrap();
+        assert_eq!(cubes.red, 20);
+        assert_eq!(cubes.green, 8);
+        assert_eq!(cubes.blue, 6);
+    }
+
+    #[test]
+    fn test_cubes_from_str_with_zero() {
+        let draw = Cubes::from_str("8 green, 6 blue").unwrap();
+        assert_eq!(draw.red, 0);
+        assert_eq!(draw.green, 8);
+        assert_eq!(draw.blue, 6);
+    }
+
+    #[test]
+    fn test_game_from_str() {
+        let game = Game::from_str("Game 5: 1 red, 2 blue, 3 green; 2 blue, 2 green").unwrap();
+        assert_eq!(game.id, 5);
+        assert_eq!(game.draws.len(), 2);
+        assert_eq!(game.draws[0], Cubes { red: 1, green: 3, blue: 2});
+        assert_eq!(game.draws[1], Cubes { red: 0, green: 2, blue: 2});
+    }
+
+    #[test]
+    fn test_with_args() {
+        let args: Vec<String> = vec![String::from("1"), String::from("2"), String::from("3")];
+        assert_eq!(Day2::with_args(&args).unwrap().bag, Cubes { red: 1, green: 2, blue: 3 });
+        assert_eq!(Day2::with_args(&[]).unwrap().bag, Cubes { red: 12, green: 13, blue: 14 });
+
+        let args: Vec<String> = vec![String::from("1"), String::from("x"), String::from("3")];
+        assert!(Day2::with_args(&args).unwrap_err().to_string().contains("'x'"));
+        assert!(Day2::with_args(&args[..2]).is_err());
+    }
+
+    #[test]
+    fn test_malformed_games() {
+        let message = |input: &str| Day2::default().parse(input).unwrap_err().to_string();
+        let error = message("Game 1: 3 blue\nGame 2: 1 purple");
+        assert!(error.contains("'purple' is not a valid cube color."));
+        assert!(error.contains("2 | Game 2: 1 purple"));
+        assert!(error.contains(":2:11"));
+
+        let error = message("Game one: 3 blue");
+        assert!(error.contains("'one' is not a number."));
+        assert!(error.contains(":1:6"));
+        let error = message("Game 1 3 blue");
+        assert!(error.contains("Expected ':'."));
+        assert!(error.contains(":1:8"));
+        let error = message("Game 1: blue");
+        assert!(error.contains("'blue' is not a number."));
+        assert!(error.contains(":1:9"));
+        assert!(message("Game 1: 3 blue;").contains("Expected a number."));
+    }
+
+    #[test]
+    fn test_game_possible() {
+        let game = Game { id: 1, draws: vec![
+            Cubes {red: 1, green: 2, blue: 3},
+            Cubes {red: 2, green: 3, blue: 4}
+        ]};
+
+        assert!(game.possible(&Cubes { red: 5, green: 5, blue: 5}));
+        assert!(!game.possible(&Cubes { red: 3, green: 3, blue: 3}));
+    }
+}
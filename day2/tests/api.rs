@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day2::{sum_of_minimum_power, sum_of_possible_games, Cubes, Day2, Game};
+
+fn example() -> Vec<Game> {
+    Day2::default().parse_input(Path::new("tests/input.txt")).unwrap()
+}
+
+#[test]
+fn test_games() {
+    let games = example();
+    assert_eq!(games.len(), 5);
+    assert_eq!(games[0].id, 1);
+    assert_eq!(games[0].required(), Cubes { red: 4, green: 2, blue: 6 });
+    assert_eq!(games[0].required().power(), 48);
+}
+
+#[test]
+fn test_part_functions() {
+    let games = example();
+    assert_eq!(sum_of_possible_games(&games, &Cubes { red: 12, green: 13, blue: 14 }), 8);
+    assert_eq!(sum_of_possible_games(&games, &Cubes { red: 20, green: 13, blue: 15 }), 15);
+    assert_eq!(sum_of_minimum_power(&games), 2286);
+}
+
+#[test]
+fn test_new() {
+    let day = Day2::new(Cubes { red: 4, green: 3, blue: 6 });
+    assert_eq!(day.part1(&example()).unwrap(), 3);
+}
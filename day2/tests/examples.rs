@@ -0,0 +1 @@
+aoc::example_tests!(day2::Day2);
@@ -0,0 +1,312 @@
+//! Day 3: Gear Ratios. An engine schematic of numbers and symbols, where the
+//! numbers next to a symbol are part numbers.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use aoc::Solution;
+use aoc::errors::AOCError;
+use aoc::grid::{Grid, Position};
+use aoc::viz::{Color, Frame, Player};
+
+static RADIX: u32 = 10;
+
+#[derive(Default)]
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Schematic;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.parse()
+    }
+
+    fn part1(&self, schematic: &Self::Input) -> Result<u64, AOCError> {
+        Ok(sum_of_parts(schematic))
+    }
+
+    fn part2(&self, schematic: &Self::Input) -> Result<u64, AOCError> {
+        gear_ratio(schematic)
+    }
+
+    fn visualize(&self, schematic: &Self::Input, player: &mut Player) -> Result<(), AOCError> {
+        show_parts_and_gears(schematic, player)
+    }
+}
+
+/// The engine schematic with the numbers found in it.
+#[derive(Debug)]
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub numbers: Vec<Number>
+}
+
+impl Schematic {
+    pub fn new(grid: Grid<char>) -> Self {
+        let numbers = numbers(&grid);
+        Self { grid, numbers }
+    }
+
+    /// The numbers next to a symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter()
+            .filter(|number| self.is_part(number))
+    }
+
+    pub fn is_part(&self, number: &Number) -> bool {
+        number.adjacent(&self.grid).any(|position| is_symbol(self.grid[position]))
+    }
+
+    /// Every `*` touching exactly two numbers with those numbers, row by row.
+    pub fn gears(&self) -> Vec<(Position, [&Number; 2])> {
+        let mut stars: HashMap<Position, Vec<&Number>> = HashMap::new();
+        for number in &self.numbers {
+            let adjacent: HashSet<Position> = number.adjacent(&self.grid)
+                .filter(|position| self.grid[*position] == '*')
+                .collect();
+            for star in adjacent {
+                stars.entry(star).or_default().push(number);
+            }
+        }
+        let mut gears: Vec<(Position, [&Number; 2])> = stars.into_iter()
+            .filter_map(|(star, numbers)| match numbers[..] {
+                [first, second] => Some((star, [first, second])),
+                _ => None
+            })
+            .collect();
+        gears.sort_by_key(|((x, y), _)| (*y, *x));
+        gears
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for (i, line) in s.lines().enumerate() {
+            check_line(line).map_err(|error| error.at_line(i + 1, line))?;
+        }
+        let grid: Grid<char> = s.parse()?;
+        if grid.is_empty() {
+            return Err(AOCError::parse("Expected at least one line in the schematic."));
+        }
+        Ok(Self::new(grid))
+    }
+}
+
+fn check_line(line: &str) -> Result<(), AOCError> {
+    for number in line.split(|c: char| !c.is_digit(RADIX)) {
+        if number.len() > 9 {
+            return Err(AOCError::parse("Expected a number with at most 9 digits.").at_column_of(line, number));
+        }
+    }
+    Ok(())
+}
+
+/// A number in the schematic, covering the columns `start..end` of its row.
+#[derive(Debug, PartialEq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize
+}
+
+impl Number {
+    /// Every position touching one of the number's digits, including diagonally.
+    pub fn adjacent<'a>(&self, grid: &'a Grid<char>) -> impl Iterator<Item = Position> + 'a {
+        let row = self.row;
+        (self.start..self.end).flat_map(move |x| grid.neighbours8((x, row)))
+    }
+}
+
+/// Anything but a digit or a period.
+pub fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_digit(RADIX)
+}
+
+/// All numbers in the schematic, row by row.
+pub fn numbers(grid: &Grid<char>) -> Vec<Number> {
+    let mut numbers: Vec<Number> = Vec::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut current: Option<Number> = None;
+        for (x, c) in row.iter().enumerate() {
+            match (c.to_digit(RADIX), current.as_mut()) {
+                (Some(digit), Some(number)) => {
+                    number.value = number.value * 10 + digit;
+                    number.end = x + 1;
+                },
+                (Some(digit), None) => current = Some(Number { value: digit, row: y, start: x, end: x + 1 }),
+                (None, _) => numbers.extend(current.take())
+            }
+        }
+        numbers.extend(current);
+    }
+    numbers
+}
+
+/// The sum of the numbers next to a symbol.
+pub fn sum_of_parts(schematic: &Schematic) -> u64 {
+    schematic.part_numbers().map(|number| u64::from(number.value)).sum()
+}
+
+/// The sum of the products of the two numbers next to every `*` that
+/// touches exactly two numbers. Nine digit numbers make it overflow after
+/// about 18 gears, that fails with [`AOCError::NoSolution`].
+pub fn gear_ratio(schematic: &Schematic) -> Result<u64, AOCError> {
+    schematic.gears().iter()
+        .try_fold(0, |sum, (_, numbers)| add_ratio(sum, *numbers))
+}
+
+fn ratio([first, second]: [&Number; 2]) -> u64 {
+    u64::from(first.value) * u64::from(second.value)
+}
+
+fn add_ratio(sum: u64, numbers: [&Number; 2]) -> Result<u64, AOCError> {
+    sum.checked_add(ratio(numbers))
+        .ok_or(AOCError::no_solution("the gear ratios add up to more than 64 bits hold"))
+}
+
+/// Goes through the numbers one by one, marking part numbers green and the
+/// others red, then through the gears with the numbers they multiply.
+pub fn show_parts_and_gears(schematic: &Schematic, player: &mut Player) -> Result<(), AOCError> {
+    let mut frame = Frame::new(&schematic.grid);
+    for (position, c) in schematic.grid.iter() {
+        frame.color(position, if is_symbol(*c) { Color::Yellow } else { Color::Gray });
+    }
+
+    let mut sum = 0;
+    for number in &schematic.numbers {
+        let (color, caption) = if schematic.is_part(number) {
+            sum += u64::from(number.value);
+            (Color::Green, format!("{} is a part number, the sum is {sum}", number.value))
+        } else {
+            (Color::Red, format!("{} touches no symbol", number.value))
+        };
+        let mut current = frame.clone().with_caption(caption);
+        for x in number.start..number.end {
+            current.highlight((x, number.row), color);
+            frame.color((x, number.row), color);
+        }
+        player.show(&current)?;
+        if player.stopped() {
+            return Ok(());
+        }
+    }
+
+    let mut ratios = 0;
+    for (star, numbers) in schematic.gears() {
+        ratios = add_ratio(ratios, numbers)?;
+        let [first, second] = numbers;
+        let mut current = frame.clone().with_caption(format!(
+            "The gear at {star:?} has the ratio {} * {} = {}, the sum is {ratios}",
+            first.value,
+            second.value,
+            ratio(numbers)
+        ));
+        current.highlight(star, Color::Magenta);
+        for number in [first, second] {
+            for x in number.start..number.end {
+                current.highlight((x, number.row), Color::Magenta);
+            }
+        }
+        frame.color(star, Color::Magenta);
+        player.show(&current)?;
+        if player.stopped() {
+            return Ok(());
+        }
+    }
+
+    let frame = frame.with_caption(format!("The part numbers sum to {sum}, the gear ratios to {ratios}"));
+    player.show(&frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let grid: Grid<char> = "..31*11\n.1234.5".parse().unwrap();
+        let values: Vec<u32> = numbers(&grid).iter().map(|number| number.value).collect();
+        assert_eq!(values, vec![31, 11, 1234, 5]);
+        assert_eq!(numbers(&grid)[2], Number { value: 1234, row: 1, start: 1, end: 5 });
+    }
+
+    #[test]
+    fn test_parts_next_to_symbols() {
+        assert_eq!(sum_of_parts(&".*...\n.12.3\n.....".parse().unwrap()), 12);
+        assert_eq!(sum_of_parts(&".52.3\n#....".parse().unwrap()), 52);
+        assert_eq!(sum_of_parts(&"...$.*....\n.664.598..".parse().unwrap()), 1262);
+    }
+
+    #[test]
+    fn test_single_line() {
+        let schematic = Day3.parse("12*3..4").unwrap();
+        assert_eq!(Day3.part1(&schematic).unwrap(), 15);
+        assert_eq!(Day3.part2(&schematic).unwrap(), 36);
+    }
+
+    #[test]
+    fn test_gears() {
+        let schematic: Schematic = "12*3..4\n..*...*\n.5...67".parse().unwrap();
+        let gears: Vec<(Position, Vec<u32>)> = schematic.gears().into_iter()
+            .map(|(star, numbers)| (star, numbers.iter().map(|number| number.value).collect()))
+            .collect();
+        assert_eq!(gears, vec![((2, 0), vec![12, 3]), ((6, 1), vec![4, 67])]);
+    }
+
+    #[test]
+    fn test_show_parts_and_gears() {
+        let schematic: Schematic = "12*3..4\n.......\n...5...".parse().unwrap();
+        let mut player = Player::recording();
+        show_parts_and_gears(&schematic, &mut player).unwrap();
+        let captions: Vec<&str> = player.frames().iter().map(|frame| frame.caption.as_str()).collect();
+        assert_eq!(captions, vec![
+            "12 is a part number, the sum is 12",
+            "3 is a part number, the sum is 15",
+            "4 touches no symbol",
+            "5 touches no symbol",
+            "The gear at (2, 0) has the ratio 12 * 3 = 36, the sum is 36",
+            "The part numbers sum to 15, the gear ratios to 36"
+        ]);
+        assert!(player.frames()[2].cells[(6, 0)].highlighted);
+        assert_eq!(player.frames()[5].cells[(6, 0)].color, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let schematic = Day3.parse("999999999*999999999").unwrap();
+        assert_eq!(Day3.part1(&schematic).unwrap(), 1_999_999_998);
+        assert_eq!(Day3.part2(&schematic).unwrap(), 999_999_998_000_000_001);
+
+        let schematic = Day3.parse(&"999999999*999999999\n...................\n".repeat(19)).unwrap();
+        assert!(Day3.part2(&schematic).unwrap_err().to_string().contains("more than 64 bits"));
+    }
+
+    #[test]
+    fn test_malformed_schematic() {
+        let message = |input: &str| Day3.parse(input).unwrap_err().to_string();
+        assert!(message("").contains("Expected at least one line"));
+
+        let error = message("467..\n...*\n..35.");
+        assert!(error.contains("Expected 5 characters like the first line, found 4."));
+        assert!(error.contains("2 | ...*"));
+
+        let error = message("..1234567890..");
+        assert!(error.contains("at most 9 digits"));
+        assert!(error.contains(":1:3"));
+    }
+
+    #[test]
+    fn test_is_symbol() {
+        assert!(is_symbol('#'));
+        assert!(is_symbol('*'));
+        assert!(!is_symbol('.'));
+        assert!(!is_symbol('7'));
+    }
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day3::{gear_ratio, sum_of_parts, Day3, Number, Schematic};
+
+fn example() -> Schematic {
+    Day3.parse_input(Path::new("tests/input.txt")).unwrap()
+}
+
+#[test]
+fn test_numbers() {
+    let schematic = example();
+    assert_eq!(schematic.numbers.len(), 10);
+    assert_eq!(schematic.numbers[0], Number { value: 467, row: 0, start: 0, end: 3 });
+    let parts: Vec<u32> = schematic.part_numbers().map(|number| number.value).collect();
+    assert_eq!(parts, vec![467, 35, 633, 617, 592, 755, 664, 598]);
+}
+
+#[test]
+fn test_part_functions() {
+    let schematic = example();
+    assert_eq!(sum_of_parts(&schematic), 4361);
+    assert_eq!(gear_ratio(&schematic).unwrap(), 467835);
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day3::Day3);
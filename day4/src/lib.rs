@@ -0,0 +1,139 @@
+//! Day 4: Scratchcards. Every card has winning numbers and numbers you have,
+//! and wins points or copies of the following cards for each match.
+
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+
+use aoc::errors::AOCError;
+use aoc::parse::{parse_line, parse_lines, Cursor, Parsed};
+use aoc::Solution;
+
+#[derive(Default)]
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<Scratchcard>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        parse_lines(input, Scratchcard::parse)
+    }
+
+    fn part1(&self, cards: &Self::Input) -> Result<u32, AOCError> {
+        check_cards(cards)
+    }
+
+    fn part2(&self, cards: &Self::Input) -> Result<u32, AOCError> {
+        count_scratchcards(cards)
+    }
+}
+
+#[derive(Debug)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    pub own: HashSet<u32>,
+}
+
+impl Scratchcard {
+    /// How many of the card's own numbers are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.winning.intersection(&self.own).count()
+    }
+
+    /// Reads a card like `Card 1: 41 48 83 | 83 86  6 31`.
+    fn parse(cursor: &mut Cursor) -> Parsed<Self> {
+        let id = cursor.labelled("Card", Cursor::unsigned::<u32>)?;
+        cursor.literal(":")?;
+        let winning = cursor.numbers()?;
+        cursor.literal("|")?;
+        let own = cursor.numbers()?;
+        Ok(Scratchcard { id, winning: winning.into_iter().collect(), own: own.into_iter().collect() })
+    }
+}
+
+impl FromStr for Scratchcard {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_line(s, Scratchcard::parse)
+    }
+}
+
+/// How many cards there are in the end, when every card wins a copy of as
+/// many following cards as it has matching numbers.
+pub fn count_scratchcards(cards: &[Scratchcard]) -> Result<u32, AOCError> {
+    let too_many = || AOCError::no_solution("there are more scratchcards than 32 bits hold");
+    let mut sum: u32 = 0;
+    let mut factors: VecDeque<u32> = VecDeque::new();
+
+    for card in cards {
+        let count = card.matches();
+
+        let current_factor = factors.pop_front().unwrap_or(1);
+        sum = sum.checked_add(current_factor).ok_or_else(too_many)?;
+
+        if count > factors.len() {
+            factors.resize(count, 1);
+        }
+        // Every copy of this card wins one of each of the next `count` cards.
+        for factor in factors.iter_mut().take(count) {
+            *factor = factor.checked_add(current_factor).ok_or_else(too_many)?;
+        }
+    }
+    Ok(sum)
+}
+
+/// The points of all cards: 1 for the first matching number of a card,
+/// doubled for every further one.
+pub fn check_cards(cards: &[Scratchcard]) -> Result<u32, AOCError> {
+    let mut sum: u32 = 0;
+    for card in cards {
+        let count = card.matches();
+        if count > 0 {
+            let points = 1u32.checked_shl(count as u32 - 1)
+                .ok_or(AOCError::no_solution(format!("card {} is worth 2^{} points, which is too many", card.id, count - 1)))?;
+            sum = sum.checked_add(points)
+                .ok_or(AOCError::no_solution("the points add up to more than 32 bits hold"))?;
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_cards() {
+        let message = |input: &str| Day4.parse(input).unwrap_err().to_string();
+        let error = message("Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30");
+        assert!(error.contains("'3x' is not a number."));
+        assert!(error.contains(":2:12"));
+        assert!(message("Card 1 41 48 | 83 86").contains("Expected ':'."));
+        assert!(message("Cart 1: 41 48 | 83 86").contains("Expected 'Card'."));
+        let error = message("Card 1: 41 48 83 86");
+        assert!(error.contains("Expected '|'"));
+        assert!(error.contains(":1:20"));
+        assert!(Day4.parse("Card 1: 41 | x").is_err());
+    }
+
+    #[test]
+    fn test_too_many_scratchcards() {
+        // Every card wins a copy of all the cards after it, doubling them.
+        let cards = |count: usize| -> Vec<Scratchcard> {
+            let input: Vec<String> = (1..=count)
+                .map(|id| {
+                    let numbers: Vec<String> = (1..=count - id).map(|number| number.to_string()).collect();
+                    format!("Card {id}: {} | {}", numbers.join(" "), numbers.join(" "))
+                })
+                .collect();
+            Day4.parse(&input.join("\n")).unwrap()
+        };
+        assert_eq!(count_scratchcards(&cards(31)).unwrap(), u32::MAX >> 1);
+        assert!(count_scratchcards(&cards(33)).unwrap_err().to_string().contains("more scratchcards than 32 bits hold"));
+    }
+}
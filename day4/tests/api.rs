@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day4::{check_cards, count_scratchcards, Day4, Scratchcard};
+
+fn example() -> Vec<Scratchcard> {
+    Day4.parse_input(Path::new("tests/input.txt")).unwrap()
+}
+
+#[test]
+fn test_part_functions() {
+    let cards = example();
+    assert_eq!(check_cards(&cards).unwrap(), 13);
+    assert_eq!(count_scratchcards(&cards).unwrap(), 30);
+}
+
+#[test]
+fn test_scratchcard() {
+    let card: Scratchcard = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".parse().unwrap();
+    assert_eq!((card.id, card.matches()), (1, 4));
+    assert!("Card 1: 41 48".parse::<Scratchcard>().is_err());
+}
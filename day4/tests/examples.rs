@@ -0,0 +1 @@
+aoc::example_tests!(day4::Day4);
@@ -0,0 +1,234 @@
+//! Day 5: If You Give A Seed A Fertilizer. An almanac of seeds and the maps
+//! that take them step by step to a location.
+
+use std::ops::Range;
+use std::str::FromStr;
+
+use aoc::errors::AOCError;
+use aoc::interval::{IntervalSet, RangeMap};
+use aoc::parse::{parse_line, sections, Section};
+use aoc::Solution;
+
+#[derive(Default)]
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    type Input = Almanac;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.parse()
+    }
+
+    fn part1(&self, almanac: &Self::Input) -> Result<i64, AOCError> {
+        nearest_location(almanac)
+    }
+
+    fn part2(&self, almanac: &Self::Input) -> Result<i64, AOCError> {
+        nearest_location_of_ranges(almanac)
+    }
+}
+
+/// The seeds and the maps that take them to a location.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    /// Every map in order, as the offsets it adds to the values in its source ranges.
+    pub maps: Vec<RangeMap<i64>>,
+}
+
+impl Almanac {
+    /// Where `seed` ends up after every map.
+    pub fn location(&self, seed: i64) -> i64 {
+        self.maps.iter().fold(seed, |value, map| map.map(value))
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sections = sections(s);
+        let (seeds, maps) = sections.split_first()
+            .ok_or(AOCError::parse("Expected a 'seeds:' line."))?;
+        Ok(Self {
+            seeds: parse_seeds(seeds)?,
+            maps: maps.iter().map(parse_map).collect::<Result<_, _>>()?
+        })
+    }
+}
+
+/// The lowest location any of the seeds maps to.
+pub fn nearest_location(almanac: &Almanac) -> Result<i64, AOCError> {
+    almanac.seeds.iter()
+        .map(|seed| almanac.location(*seed))
+        .min()
+        .ok_or(AOCError::parse("Expected at least one seed."))
+}
+
+/// The lowest location of any seed when the seeds line lists ranges of
+/// seeds. Maps whole ranges through every step instead of single seeds.
+pub fn nearest_location_of_ranges(almanac: &Almanac) -> Result<i64, AOCError> {
+    almanac.maps.iter()
+        .fold(seed_ranges(almanac)?, |values, map| map.map_set(&values))
+        .min()
+        .ok_or(AOCError::no_solution("all seed ranges are empty"))
+}
+
+/// The seeds read as pairs of range start and length.
+pub fn seed_ranges(almanac: &Almanac) -> Result<IntervalSet<i64>, AOCError> {
+    if !almanac.seeds.len().is_multiple_of(2) {
+        return Err(AOCError::parse("Expected pairs of seed range start and length."));
+    }
+    almanac.seeds
+        .chunks(2)
+        .map(|chunk| range(chunk[0], chunk[1]))
+        .collect()
+}
+
+/// `start..start + length`, unless its end doesn't fit in an `i64`.
+fn range(start: i64, length: i64) -> Result<Range<i64>, AOCError> {
+    start.checked_add(length)
+        .map(|end| start..end)
+        .ok_or(AOCError::parse(format!("The range from {start} with length {length} doesn't fit in 64 bits.")))
+}
+
+/// The `seeds:` line, alone in the first section.
+fn parse_seeds(section: &Section) -> Result<Vec<i64>, AOCError> {
+    let mut lines = section.numbered();
+    let (number, line) = lines.next()
+        .ok_or(AOCError::parse("Expected a 'seeds:' line."))?;
+    if let Some((number, line)) = lines.next() {
+        return Err(AOCError::parse("Expected a blank line after the seeds.").at_line(number, line));
+    }
+    let seeds = parse_line(line, |cursor| {
+        if !cursor.try_literal("seeds:") {
+            return Err(cursor.error("Expected the first line to start with 'seeds:'."));
+        }
+        cursor.numbers()
+    }).map_err(|error| error.at_line(number, line))?;
+    if seeds.is_empty() {
+        return Err(AOCError::parse("Expected at least one seed.").at_line(number, line));
+    }
+    for pair in seeds.chunks_exact(2) {
+        range(pair[0], pair[1]).map_err(|error| error.at_line(number, line))?;
+    }
+    Ok(seeds)
+}
+
+/// A section with a `<name> map:` header and a line of destination, source
+/// and length for every range of the map.
+fn parse_map(section: &Section) -> Result<RangeMap<i64>, AOCError> {
+    let (header, ranges) = section.split_header()
+        .ok_or(AOCError::parse("Expected a map."))?;
+    parse_line(header, |cursor| {
+        let name = cursor.until(" map:").unwrap_or_default();
+        if name.trim().is_empty() {
+            return Err(cursor.error("Expected a header like 'seed-to-soil map:'."));
+        }
+        cursor.literal("map:")
+    }).map_err(|error| error.at_line(section.first_line, header))?;
+    let ranges = ranges.parse_lines(|cursor| {
+        let numbers: Vec<i64> = cursor.numbers()?;
+        let [destination, source, length] = numbers[..] else {
+            return Err(AOCError::parse(format!(
+                "Expected destination, source and length, found {} numbers.", numbers.len()
+            )));
+        };
+        // The values of the source range end up in the destination range.
+        range(destination, length)?;
+        let shift = destination.checked_sub(source)
+            .ok_or(AOCError::parse(format!("The distance from {source} to {destination} doesn't fit in 64 bits.")))?;
+        Ok((range(source, length)?, shift))
+    })?;
+
+    let mut map = RangeMap::new();
+    for (source, shift) in ranges {
+        map.insert(source, shift);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc::read_input;
+
+    use super::*;
+
+    fn input(path: &str) -> Almanac {
+        Day5.parse(&read_input(Path::new(path)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_malformed_almanac() {
+        let message = |input: &str| Day5.parse(input).and_then(|almanac| Day5.part2(&almanac)).unwrap_err().to_string();
+        assert!(message("").contains("Expected a 'seeds:' line."));
+        assert!(message("seed: 1 2").contains("start with 'seeds:'"));
+        assert!(message("seeds: 79 14 55").contains("Expected pairs"));
+        assert!(message("seeds: 79 14\nseed-to-soil map:\n50 98 2").contains("Expected a blank line after the seeds."));
+
+        let error = message("seeds: 79 14\n\nseed-to-soil map:\n50 98\n");
+        assert!(error.contains("found 2 numbers."));
+        assert!(error.contains("4 | 50 98"));
+
+        let error = message("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\nbogus header\n");
+        assert!(error.contains("'bogus' is not a number."));
+        assert!(error.contains("5 | bogus header"));
+        assert!(message("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nbogus header\n").contains("Expected a header"));
+        assert!(message("seeds: 79 14\n\nsoil map: extra\n").contains("Unexpected 'extra'."));
+
+        let error = message("seeds: 9223372036854775807 1\n\nseed-to-soil map:\n50 98 2\n");
+        assert!(error.contains("doesn't fit in 64 bits."));
+        assert!(error.contains("1 | seeds:"));
+        let error = message("seeds: 0 5\n\nseed-to-soil map:\n9223372036854775807 0 5\n");
+        assert!(error.contains("The range from 9223372036854775807 with length 5 doesn't fit in 64 bits."));
+        assert!(error.contains("4 | 9223372036854775807 0 5"));
+        assert!(message("seeds: 0 5\n\nseed-to-soil map:\n9223372036854775806 -2 1\n").contains("The distance from -2"));
+        assert!(message("seeds: 0 5\n\nseed-to-soil map:\n1 9223372036854775807 1\n").contains("doesn't fit in 64 bits."));
+
+        let error = message("seeds: 79 1x");
+        assert!(error.contains("'1x' is not a number."));
+        assert!(error.contains(":1:11"));
+    }
+
+    /// Maps every single seed, which is only feasible on small almanacs.
+    fn brute_force(almanac: &Almanac) -> i64 {
+        seed_ranges(almanac).unwrap().iter()
+            .flat_map(|range| range.start..range.end)
+            .map(|seed| almanac.location(seed))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_part2_matches_brute_force() {
+        assert_eq!(nearest_location_of_ranges(&input("tests/input.txt")).unwrap(), brute_force(&input("tests/input.txt")));
+
+        // Small almanacs from a fixed linear congruential generator, with
+        // overlapping seed ranges and maps touching and crossing each other.
+        let mut state: u64 = 42;
+        let mut next = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % bound) as i64
+        };
+        for _ in 0..50 {
+            let mut almanac = String::from("seeds:");
+            for _ in 0..1 + next(3) {
+                almanac += &format!(" {} {}", next(100), 1 + next(20));
+            }
+            for step in 0..1 + next(4) {
+                almanac += &format!("\n\nstep-{step} map:");
+                for _ in 0..1 + next(4) {
+                    almanac += &format!("\n{} {} {}", next(120), next(120), 1 + next(30));
+                }
+            }
+            let parsed = Day5.parse(&almanac).unwrap();
+            assert_eq!(nearest_location_of_ranges(&parsed).unwrap(), brute_force(&parsed), "{almanac}");
+        }
+    }
+}
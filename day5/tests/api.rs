@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day5::{nearest_location, nearest_location_of_ranges, seed_ranges, Almanac, Day5};
+
+fn example() -> Almanac {
+    Day5.parse_input(Path::new("tests/input.txt")).unwrap()
+}
+
+#[test]
+fn test_almanac() {
+    let almanac = example();
+    assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+    assert_eq!(almanac.maps.len(), 7);
+    assert_eq!(almanac.location(79), 82);
+    assert_eq!(seed_ranges(&almanac).unwrap().iter().count(), 2);
+}
+
+#[test]
+fn test_part_functions() {
+    let almanac = example();
+    assert_eq!(nearest_location(&almanac).unwrap(), 35);
+    assert_eq!(nearest_location_of_ranges(&almanac).unwrap(), 46);
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day5::Day5);
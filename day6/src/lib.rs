@@ -0,0 +1,135 @@
+//! Day 6: Wait For It. Boat races where holding the button longer makes the
+//! boat faster but leaves less time to move.
+
+use std::str::FromStr;
+
+use aoc::errors::AOCError;
+use aoc::Solution;
+
+#[derive(Default)]
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Input = Races;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.parse()
+    }
+
+    fn part1(&self, races: &Self::Input) -> Result<u64, AOCError> {
+        Ok(product_of_ways(races))
+    }
+
+    fn part2(&self, races: &Self::Input) -> Result<u64, AOCError> {
+        Ok(ways_for_one_race(races))
+    }
+}
+
+/// The time and record distance of every race, and of the single race the
+/// sheet describes with the spaces between the digits removed.
+#[derive(Debug, PartialEq)]
+pub struct Races {
+    pub races: Vec<(u64, u64)>,
+    pub joined: (u64, u64),
+}
+
+impl FromStr for Races {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let mut values: Vec<Vec<u64>> = Vec::new();
+        let mut joined: Vec<u64> = Vec::new();
+        for (i, label) in ["Time:", "Distance:"].iter().enumerate() {
+            let line = lines.next()
+                .ok_or(AOCError::parse(format!("Expected a '{label}' line.")))?;
+            let numbers = line.strip_prefix(label)
+                .ok_or(AOCError::parse(format!("Expected the line to start with '{label}'.")).at_line(i + 1, line))?;
+            values.push(parse_numbers(line, numbers).map_err(|error| error.at_line(i + 1, line))?);
+            joined.push(join_numbers(numbers).map_err(|error| error.at_line(i + 1, line))?);
+        }
+        if values[0].len() != values[1].len() {
+            return Err(AOCError::parse(format!("Expected {} distances, found {}.", values[0].len(), values[1].len()))
+                .at_line(2, s.lines().nth(1).unwrap_or_default()));
+        }
+        Ok(Self {
+            races: values[0].iter().copied().zip(values[1].iter().copied()).collect(),
+            joined: (joined[0], joined[1])
+        })
+    }
+}
+
+/// The number of ways to go further than `distance` in a race lasting `time`.
+// v * (t -v) = d
+// v^2 - tv + d = 0
+// v = (t +- sqrt(t^2 - 4d)) / 2
+pub fn race(time: u64, distance: u64) -> u64 {
+    let t = time as f64;
+    let d = distance as f64;
+
+    let discriminant = t.powi(2) - 4.0 * d;
+    if discriminant < 0.0 {
+        return 0;
+    }
+
+    let a = t / 2.0;
+    let b = discriminant.sqrt() / 2.0;
+
+    let lower = (a - b + 1.0).floor() as u64;
+    let upper = (a + b - 1.0).ceil() as u64;
+
+    (upper + 1).saturating_sub(lower)
+}
+
+/// The product of the number of ways to win every race.
+pub fn product_of_ways(races: &Races) -> u64 {
+    races.races.iter()
+        .map(|(time, distance)| race(*time, *distance))
+        .product()
+}
+
+/// The number of ways to win the single race whose time and distance are
+/// the digits of each line joined together.
+pub fn ways_for_one_race(races: &Races) -> u64 {
+    let (time, distance) = races.joined;
+    race(time, distance)
+}
+
+fn parse_numbers(line: &str, numbers: &str) -> Result<Vec<u64>, AOCError> {
+    numbers.split_whitespace()
+        .map(|number| number.parse::<u64>()
+            .map_err(|_| AOCError::parse(format!("'{number}' is not a number.")).at_column_of(line, number)))
+        .collect()
+}
+
+fn join_numbers(numbers: &str) -> Result<u64, AOCError> {
+    numbers.replace(' ', "").parse::<u64>()
+        .map_err(|_| AOCError::parse("Expected the numbers to form a single number when joined."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_race_with_single_record_time() {
+        assert_eq!(race(4, 4), 0);
+        assert_eq!(race(1, 5), 0);
+    }
+
+    #[test]
+    fn test_malformed_races() {
+        let message = |input: &str| Day6.parse(input).unwrap_err().to_string();
+        assert!(message("Time: 7 15").contains("Expected a 'Distance:' line."));
+        assert!(message("Time: 7\nDist: 9").contains("start with 'Distance:'"));
+        assert!(message("Time: 7 15\nDistance: 9").contains("Expected 2 distances, found 1."));
+
+        let error = message("Time: 7 1.5\nDistance: 9 40");
+        assert!(error.contains("'1.5' is not a number."));
+        assert!(error.contains(":1:9"));
+    }
+}
@@ -0,0 +1,18 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day6::{product_of_ways, race, ways_for_one_race, Day6, Races};
+
+#[test]
+fn test_part_functions() {
+    let races = Day6.parse_input(Path::new("tests/input.txt")).unwrap();
+    assert_eq!(races, Races { races: vec![(7, 9), (15, 40), (30, 200)], joined: (71530, 940200) });
+    assert_eq!(product_of_ways(&races), 288);
+    assert_eq!(ways_for_one_race(&races), 71503);
+}
+
+#[test]
+fn test_race() {
+    assert_eq!(race(7, 9), 4);
+    assert_eq!(race(30, 200), 9);
+}
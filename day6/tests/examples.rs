@@ -0,0 +1 @@
+aoc::example_tests!(day6::Day6);
@@ -0,0 +1,217 @@
+//! Day 7: Camel Cards. Hands of five cards ranked like poker, optionally
+//! with jokers standing in for whatever card makes the hand strongest.
+
+use std::str::FromStr;
+
+use aoc::errors::AOCError;
+use aoc::Solution;
+
+static RADIX: u32 = 10;
+
+/// The kinds of hands from weakest to strongest.
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum HandType {
+    HighCard,
+    Pair,
+    TwoPairs,
+    Three,
+    FullHouse,
+    Four,
+    Five
+}
+
+/// The cards of a hand and what was bet on it.
+#[derive(Debug, PartialEq)]
+pub struct Hand {
+    pub cards: Vec<char>,
+    pub bet: u64
+}
+
+impl Hand {
+    /// Orders hands by their type first and then card by card.
+    pub fn strength(&self, with_joker: bool) -> u64 {
+        let mut value: u64 = (get_hand_type(&self.cards, with_joker) as u64) << (5 * 8);
+        for (i, card) in self.cards.iter().enumerate() {
+            // Every card was checked when parsing the hand.
+            value |= get_card_value(card, with_joker).unwrap_or(0) << (8 * (4 - i))
+        }
+        value
+    }
+}
+
+impl FromStr for Hand {
+    type Err = AOCError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (cards, bet) = line.split_once(' ')
+            .ok_or(AOCError::parse("Expected the cards and the bet separated by a space."))?;
+        for (i, card) in cards.char_indices() {
+            get_card_value(&card, false).map_err(|error| error.at_column_of(line, &cards[i..]))?;
+        }
+        let bet = bet.trim().parse::<u64>()
+            .map_err(|_| AOCError::parse(format!("'{bet}' is not a valid bet.")).at_column_of(line, bet))?;
+        let cards: Vec<char> = cards.chars().collect();
+        if cards.len() != 5 {
+            return Err(AOCError::parse(format!("Expected 5 cards, found {}.", cards.len())).at_column(1));
+        }
+        Ok(Self { cards, bet })
+    }
+}
+
+fn get_card_value(c: &char, with_joker: bool) -> Result<u64, AOCError> {
+    match c {
+        'A' => Ok(15),
+        'K' => Ok(14),
+        'Q' => Ok(13),
+        'J' => Ok(if with_joker {1} else {12}),
+        'T' => Ok(11),
+        '2'..='9' => c.to_digit(RADIX)
+            .map(u64::from)
+            .ok_or(AOCError::parse(format!("'{c}' is not a valid card."))),
+        _ => Err(AOCError::parse(format!("'{c}' is not a valid card, expected one of AKQJT98765432.")))
+    }
+}
+
+/// The kind of a hand, counting every `J` as a joker `with_joker`.
+pub fn get_hand_type(cards: &[char], with_joker: bool) -> HandType {
+    let mut sorted = cards.to_vec();
+    sorted.sort();
+
+    let mut hand_type: HandType = HandType::HighCard;
+    let mut previous: char = '?';
+    let mut row = 0;
+    let mut jokers: i32 = 0;
+    for c in sorted {
+        if previous == c {
+            row += 1;
+        } else {
+            hand_type = match row {
+                4 => HandType::Four,
+                3 => HandType::Three,
+                2 => if hand_type == HandType::Pair {HandType::TwoPairs} else {HandType::Pair}
+                _ => hand_type
+            };
+            if with_joker && c == 'J' {
+                jokers += 1;
+                previous = '?';
+                row = 0;
+            } else {
+                row = 1;
+                previous = c;
+            }
+        }
+    }
+    hand_type = match row {
+        5 => HandType::Five,
+        4 => HandType::Four,
+        3 => if hand_type == HandType::Pair {HandType::FullHouse} else {HandType::Three}
+        2 => match hand_type {
+            HandType::Three => HandType::FullHouse,
+            HandType::Pair => HandType::TwoPairs,
+            _ => HandType::Pair
+        },
+        _ => hand_type
+    };
+    if with_joker && jokers > 0 {
+        match jokers {
+            j if j > 3 => HandType::Five,
+            3 => if hand_type == HandType::Pair {HandType::Five} else {HandType::Four},
+            2 => match hand_type {
+                HandType::Three => HandType::Five,
+                HandType::Pair => HandType::Four,
+                _ => HandType::Three
+            },
+            1 => match hand_type {
+                HandType::Four => HandType::Five,
+                HandType::Three => HandType::Four,
+                HandType::TwoPairs => HandType::FullHouse,
+                HandType::Pair => HandType::Three,
+                _ => HandType::Pair
+            },
+            _ => hand_type
+        }
+    } else {
+        hand_type
+    }
+}
+
+#[derive(Default)]
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    type Input = Vec<Hand>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.lines().enumerate()
+            .map(|(i, line)| line.parse().map_err(|error: AOCError| error.at_line(i + 1, line)))
+            .collect()
+    }
+
+    fn part1(&self, hands: &Self::Input) -> Result<u64, AOCError> {
+        Ok(total_winnings(hands, false))
+    }
+
+    fn part2(&self, hands: &Self::Input) -> Result<u64, AOCError> {
+        Ok(total_winnings(hands, true))
+    }
+}
+
+/// The sum of every hand's bet times its rank among all hands.
+pub fn total_winnings(hands: &[Hand], with_joker: bool) -> u64 {
+    let mut ranked: Vec<(u64, u64)> = hands.iter()
+        .map(|hand| (hand.strength(with_joker), hand.bet))
+        .collect();
+    ranked.sort();
+
+    let mut result: u64 = 0;
+    for (i, (_, bet)) in ranked.iter().enumerate() {
+        result += (i as u64 + 1) * bet;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part2_problems() {
+        assert_eq!(get_hand_type(&['J', '9', '9', 'T', 'T'], true), HandType::FullHouse);
+    }
+
+    #[test]
+    fn test_get_hand_type_part2() {
+        assert_eq!(get_hand_type(&"32T3K".chars().collect::<Vec<char>>(), true), HandType::Pair);
+        assert_eq!(get_hand_type(&"T55J5".chars().collect::<Vec<char>>(), true), HandType::Four);
+        assert_eq!(get_hand_type(&"KK677".chars().collect::<Vec<char>>(), true), HandType::TwoPairs);
+        assert_eq!(get_hand_type(&"KTJJT".chars().collect::<Vec<char>>(), true), HandType::Four);
+        assert_eq!(get_hand_type(&"QQQJA".chars().collect::<Vec<char>>(), true), HandType::Four);
+    }
+
+    #[test]
+    fn test_get_hand_type() {
+        assert_eq!(get_hand_type(&"AAAAA".chars().collect::<Vec<char>>(), false), HandType::Five);
+        assert_eq!(get_hand_type(&"AA8AA".chars().collect::<Vec<char>>(), false), HandType::Four);
+        assert_eq!(get_hand_type(&"23332".chars().collect::<Vec<char>>(), false), HandType::FullHouse);
+        assert_eq!(get_hand_type(&"TTT98".chars().collect::<Vec<char>>(), false), HandType::Three);
+        assert_eq!(get_hand_type(&"23432".chars().collect::<Vec<char>>(), false), HandType::TwoPairs);
+        assert_eq!(get_hand_type(&"A23A4".chars().collect::<Vec<char>>(), false), HandType::Pair);
+        assert_eq!(get_hand_type(&"23456".chars().collect::<Vec<char>>(), false), HandType::HighCard);
+    }
+
+    #[test]
+    fn test_malformed_hands() {
+        let message = |input: &str| Day7.parse(input).unwrap_err().to_string();
+        let error = message("32T3K 765\nT55X5 684");
+        assert!(error.contains("'X' is not a valid card"));
+        assert!(error.contains(":2:4"));
+        assert!(message("32T3K").contains("separated by a space"));
+        assert!(message("32T3 765").contains("Expected 5 cards, found 4."));
+        assert!(message("32T3K 7x").contains("'7x' is not a valid bet."));
+        assert!(message("3211K 765").contains("'1' is not a valid card"));
+    }
+}
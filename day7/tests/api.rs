@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day7::{get_hand_type, total_winnings, Day7, Hand, HandType};
+
+#[test]
+fn test_total_winnings() {
+    let hands = Day7.parse_input(Path::new("tests/input.txt")).unwrap();
+    assert_eq!(hands[0], Hand { cards: vec!['3', '2', 'T', '3', 'K'], bet: 765 });
+    assert_eq!(total_winnings(&hands, false), 6440);
+    assert_eq!(total_winnings(&hands, true), 5905);
+}
+
+#[test]
+fn test_get_hand_type() {
+    let cards = |hand: &str| hand.chars().collect::<Vec<char>>();
+    assert_eq!(get_hand_type(&cards("KTJJT"), false), HandType::TwoPairs);
+    assert_eq!(get_hand_type(&cards("KTJJT"), true), HandType::Four);
+    assert!(HandType::FullHouse > HandType::Three);
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day7::Day7);
@@ -0,0 +1,434 @@
+//! Day 8: Haunted Wasteland. A network of nodes with a left and a right
+//! neighbour, walked by following a list of directions over and over.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use aoc::Solution;
+use aoc::errors::AOCError;
+use aoc::graph::{find_cycle, Cycle, Graph, NodeId};
+use aoc::grid::Grid;
+use aoc::math::crt;
+use aoc::parse::{parse_line, Cursor, Parsed};
+use aoc::viz::{Color, Frame, Player};
+
+#[derive(Default)]
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = Network;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.parse()
+    }
+
+    fn part1(&self, network: &Self::Input) -> Result<u64, AOCError> {
+        steps_to_zzz(network)
+    }
+
+    fn part2(&self, network: &Self::Input) -> Result<u64, AOCError> {
+        ghost_steps(network)
+    }
+
+    fn visualize(&self, network: &Self::Input, player: &mut Player) -> Result<(), AOCError> {
+        walk_ghosts(network, player)
+    }
+}
+
+/// Every ghost walks into a loop. Its visits to Z nodes before the loop
+/// happen once, the ones on the loop repeat with the loop length. All ghosts
+/// meet at the earliest step which is a visit for every one of them.
+pub fn ghost_steps(network: &Network) -> Result<u64, AOCError> {
+    let ghosts: Vec<Ghost> = network.nodes.nodes()
+        .filter(|(_, name)| name.ends_with('A'))
+        .map(|(start, _)| Ghost::new(network, start))
+        .collect();
+    if ghosts.is_empty() {
+        return Err(AOCError::no_solution("there is no node ending in 'A' to start from"));
+    }
+    if let Some(ghost) = ghosts.iter().find(|ghost| ghost.arrivals.is_empty()) {
+        return Err(AOCError::no_solution(format!(
+            "the ghost starting at {} never reaches a node ending in 'Z'", network.nodes.node(ghost.start)
+        )));
+    }
+
+    let before_loops = ghosts.iter()
+        .flat_map(|ghost| ghost.arrivals.iter().filter(|step| **step < ghost.cycle.offset))
+        .copied()
+        .filter(|step| ghosts.iter().all(|ghost| ghost.arrives_at(*step)))
+        .min();
+    before_loops.or_else(|| on_loops(&ghosts))
+        .map(|steps| steps as u64)
+        .ok_or(AOCError::no_solution("the ghosts are never on nodes ending in 'Z' at the same time"))
+}
+
+/// The earliest step at which all ghosts are on a Z node of their loops,
+/// trying every combination of their Z nodes with the CRT.
+fn on_loops(ghosts: &[Ghost]) -> Option<usize> {
+    let latest_offset = ghosts.iter().map(|ghost| ghost.cycle.offset).max().unwrap_or(0);
+    let mut combinations: Vec<(i64, i64)> = vec![(0, 1)];
+    for ghost in ghosts {
+        let residues: Vec<(i64, i64)> = ghost.arrivals.iter()
+            .filter(|step| **step >= ghost.cycle.offset)
+            .map(|step| ((step % ghost.cycle.length) as i64, ghost.cycle.length as i64))
+            .collect();
+        combinations = combinations.iter()
+            .flat_map(|combination| residues.iter().filter_map(|residue| crt([*combination, *residue])))
+            .collect();
+    }
+    combinations.into_iter()
+        .map(|(residue, modulus)| {
+            // Step 0 is the start, the ghosts have to take at least one step.
+            let earliest = latest_offset.max(1) as i64;
+            let rounds = ((earliest - residue).max(0) + modulus - 1) / modulus;
+            (residue + rounds * modulus) as usize
+        })
+        .min()
+}
+
+/// How many steps [`walk_ghosts`] shows before it skips to the answer.
+const SHOWN_STEPS: usize = 200;
+/// How many of the latest nodes of every ghost are shown.
+const TRAIL: usize = 8;
+
+/// Walks all ghosts a step per frame, a row per ghost with the last nodes it
+/// visited and nodes ending in 'Z' in green. Real inputs take far too many
+/// steps to show, so the last frame gives the step [`ghost_steps`] found.
+pub fn walk_ghosts(network: &Network, player: &mut Player) -> Result<(), AOCError> {
+    let mut starts: Vec<(NodeId, &String)> = network.nodes.nodes()
+        .filter(|(_, name)| name.ends_with('A'))
+        .collect();
+    starts.sort_by_key(|(_, name)| *name);
+    let mut trails: Vec<VecDeque<NodeId>> = starts.iter().map(|(start, _)| VecDeque::from([*start])).collect();
+    let mut states: Vec<State> = starts.iter().map(|(start, _)| (*start, 0)).collect();
+
+    for step in 0..=SHOWN_STEPS {
+        let arrived = states.iter().filter(|(node, _)| network.nodes.node(*node).ends_with('Z')).count();
+        let direction = network.directions[states.first().map_or(0, |(_, i)| *i)];
+        let frame = trail_frame(network, &trails)?
+            .with_caption(format!("Step {step}, going {direction} next: {arrived} of {} ghosts on a Z node", states.len()));
+        player.show(&frame)?;
+        if player.stopped() || (step > 0 && arrived == states.len()) {
+            return Ok(());
+        }
+        for (state, trail) in states.iter_mut().zip(&mut trails) {
+            *state = network.next_state(*state);
+            trail.push_back(state.0);
+            if trail.len() > TRAIL {
+                trail.pop_front();
+            }
+        }
+    }
+    let steps = ghost_steps(network)?;
+    let frame = trail_frame(network, &trails)?.with_caption(format!("All ghosts are on a Z node after {steps} steps"));
+    player.show(&frame)
+}
+
+/// A row per ghost with its latest nodes, the current one highlighted.
+fn trail_frame(network: &Network, trails: &[VecDeque<NodeId>]) -> Result<Frame, AOCError> {
+    let width = network.nodes.nodes().map(|(_, name)| name.chars().count()).max().unwrap_or(0) + 1;
+    let rows: Vec<Vec<char>> = trails.iter()
+        .map(|trail| {
+            let mut row = vec![' '; width * TRAIL];
+            for (i, node) in trail.iter().enumerate() {
+                for (j, c) in network.nodes.node(*node).chars().enumerate() {
+                    row[i * width + j] = c;
+                }
+            }
+            row
+        })
+        .collect();
+    let mut frame = Frame::new(&Grid::from_rows(rows)?);
+    for (y, trail) in trails.iter().enumerate() {
+        for (i, node) in trail.iter().enumerate() {
+            let name = network.nodes.node(*node);
+            let color = if name.ends_with('Z') { Color::Green } else { Color::Gray };
+            for x in i * width..i * width + name.chars().count() {
+                if i + 1 == trail.len() {
+                    frame.highlight((x, y), color);
+                } else {
+                    frame.color((x, y), color);
+                }
+            }
+        }
+    }
+    Ok(frame)
+}
+
+/// Where a ghost's walk starts looping and the steps after which it stands
+/// on a node ending in 'Z', up to the end of its first time around the loop.
+struct Ghost {
+    start: NodeId,
+    cycle: Cycle,
+    arrivals: Vec<usize>
+}
+
+impl Ghost {
+    fn new(network: &Network, start: NodeId) -> Self {
+        let cycle = find_cycle((start, 0), |state| network.next_state(*state));
+        let mut state = (start, 0);
+        let mut arrivals = Vec::new();
+        for step in 1..=cycle.offset + cycle.length {
+            state = network.next_state(state);
+            if network.nodes.node(state.0).ends_with('Z') {
+                arrivals.push(step);
+            }
+        }
+        Self { start, cycle, arrivals }
+    }
+
+    fn arrives_at(&self, step: usize) -> bool {
+        if step < self.cycle.offset {
+            return self.arrivals.contains(&step);
+        }
+        let phase = (step - self.cycle.offset) % self.cycle.length;
+        self.arrivals.contains(&(self.cycle.offset + phase))
+            || self.arrivals.contains(&(self.cycle.offset + phase + self.cycle.length))
+    }
+}
+
+/// How many steps it takes to walk from AAA to ZZZ.
+pub fn steps_to_zzz(network: &Network) -> Result<u64, AOCError> {
+    let start = network.nodes.id("AAA")
+        .ok_or(AOCError::no_solution("there is no node AAA to start from"))?;
+    let end = network.nodes.id("ZZZ")
+        .ok_or(AOCError::no_solution("there is no node ZZZ"))?;
+
+    let cycle = find_cycle((start, 0), |state| network.next_state(*state));
+    let mut state = (start, 0);
+    for steps in 1..=(cycle.offset + cycle.length) as u64 {
+        state = network.next_state(state);
+        if state.0 == end {
+            return Ok(steps);
+        }
+    }
+    Err(AOCError::no_solution("ZZZ is never reached"))
+}
+
+/// The directions to follow and the nodes, where every node has an 'L' and
+/// an 'R' edge.
+#[derive(Debug)]
+pub struct Network {
+    pub directions: Vec<char>,
+    pub nodes: Graph<String, char>,
+}
+
+/// Where a walk is: the current node and the index of the next direction.
+pub type State = (NodeId, usize);
+
+impl Network {
+    /// Takes the next step of a walk.
+    pub fn next_state(&self, (node, i): State) -> State {
+        let next = self.nodes.edges(node)
+            .find(|(_, side)| **side == self.directions[i])
+            .map_or(node, |(next, _)| next);
+        (next, (i + 1) % self.directions.len())
+    }
+}
+
+impl FromStr for Network {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+        parse_network(&lines)
+    }
+}
+
+fn parse_network(lines: &[&str]) -> Result<Network, AOCError> {
+    let first = lines.first()
+        .ok_or(AOCError::parse("Expected a line of L and R directions."))?;
+    if first.is_empty() {
+        return Err(AOCError::parse("Expected at least one direction.").at_line(1, first));
+    }
+    if let Some((i, c)) = first.char_indices().find(|(_, c)| *c != 'L' && *c != 'R') {
+        return Err(AOCError::parse(format!("'{c}' is not a direction, expected L or R."))
+            .at_column_of(first, &first[i..])
+            .at_line(1, first));
+    }
+    let directions: Vec<char> = first.chars().collect();
+
+    if lines.get(1).is_some_and(|line| !line.trim().is_empty()) {
+        return Err(AOCError::parse("Expected an empty line after the directions.").at_line(2, lines[1]));
+    }
+
+    let mut graph: Graph<String, char> = Graph::new();
+    let mut nodes: Vec<(usize, NodeId, (&str, &str))> = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(2) {
+        let (node, choices) = parse_line(line, parse_node).map_err(|error| error.at_line(i + 1, line))?;
+        nodes.push((i, graph.intern(node.to_owned()), choices));
+    }
+
+    for (i, node, (left, right)) in nodes {
+        for (side, choice) in [('L', left), ('R', right)] {
+            let Some(next) = graph.id(choice) else {
+                let line = lines[i];
+                return Err(AOCError::parse(format!("Node '{choice}' is not defined."))
+                    .at_column_of(line, choice)
+                    .at_line(i + 1, line));
+            };
+            graph.add_edge(node, next, side);
+        }
+    }
+    Ok(Network { directions, nodes: graph })
+}
+
+/// Reads a node like `AAA = (BBB, CCC)`.
+fn parse_node<'a>(cursor: &mut Cursor<'a>) -> Parsed<(&'a str, (&'a str, &'a str))> {
+    let node = cursor.word()?;
+    cursor.literal("=")?;
+    cursor.literal("(")?;
+    let left = cursor.word()?;
+    cursor.literal(",")?;
+    let right = cursor.word()?;
+    cursor.literal(")")?;
+    Ok((node, (left, right)))
+}
+
+/// Draws the network in Graphviz DOT with the nodes on the way from AAA to ZZZ filled.
+pub fn to_dot(network: &Network) -> String {
+    let nodes = &network.nodes;
+    let mut path: Vec<NodeId> = Vec::new();
+    if let (Some(start), Some(end)) = (nodes.id("AAA"), nodes.id("ZZZ")) {
+        let cycle = find_cycle((start, 0), |state| network.next_state(*state));
+        let mut state = (start, 0);
+        path.push(start);
+        for _ in 0..cycle.offset + cycle.length {
+            state = network.next_state(state);
+            path.push(state.0);
+            if state.0 == end {
+                break;
+            }
+        }
+    }
+    nodes.to_dot(&path, |side| Some(side.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc::read_input;
+
+    use super::*;
+
+    fn input(path: &str) -> Network {
+        Day8.parse(&read_input(Path::new(path)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_walk_ghosts() {
+        let mut player = Player::recording();
+        walk_ghosts(&input("tests/part2.txt"), &mut player).unwrap();
+        let frames = player.frames();
+        assert_eq!(frames.len(), 7);
+        assert_eq!(frames[0].caption, "Step 0, going L next: 0 of 2 ghosts on a Z node");
+        assert_eq!(frames[6].caption, "Step 6, going L next: 2 of 2 ghosts on a Z node");
+        let rows: Vec<String> = frames[2].render(false).lines().map(|row| row.trim_end().to_owned()).collect();
+        assert_eq!(rows, vec!["11A 11B ###", "22A 22B ###", "Step 2, going L next: 1 of 2 ghosts on a Z node"]);
+        assert_eq!(frames[2].cells[(8, 0)].color, Some(Color::Green));
+        assert_eq!(frames[2].cells[(8, 1)].color, Some(Color::Gray));
+
+        // Ghosts that never meet are followed for a while, then the answer ends it.
+        let mut player = Player::recording();
+        let network = Day8.parse("L\n\nAAA = (BBB, BBB)\nBBB = (CCC, CCC)\nCCC = (AAA, AAA)").unwrap();
+        assert!(walk_ghosts(&network, &mut player).unwrap_err().to_string().contains("never reaches"));
+        assert_eq!(player.frames().len(), SHOWN_STEPS + 1);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&input("tests/input1.txt"));
+        assert_eq!(dot.matches("fillcolor").count(), 3);
+        assert_eq!(dot.matches(" -> ").count(), 14);
+    }
+
+    #[test]
+    fn test_malformed_network() {
+        let message = |input: &str| Day8.parse(input).unwrap_err().to_string();
+        assert!(message("").contains("Expected a line of L and R directions."));
+        assert!(message("LX\n\nAAA = (AAA, AAA)").contains(":1:2"));
+        assert!(message("LR\nAAA = (AAA, AAA)").contains("Expected an empty line"));
+        assert!(message("LR\n\nAAA (AAA, AAA)").contains("Expected '='."));
+        assert!(message("LR\n\nAAA = AAA, AAA").contains("Expected '('."));
+        let error = message("LR\n\nAAA = (AAA AAA)");
+        assert!(error.contains("Expected ','."));
+        assert!(error.contains(":3:12"));
+        assert!(message("LR\n\nAAA = (AAA, AAA) BBB").contains("Unexpected 'BBB'."));
+
+        let error = message("LR\n\nAAA = (AAA, ZZZ)\nBBB = (BBB, CCC)");
+        assert!(error.contains("Node 'ZZZ' is not defined."));
+        assert!(error.contains(":3:13"));
+    }
+
+    #[test]
+    fn test_missing_start() {
+        let network = Day8.parse("LR\n\nBBB = (BBB, BBB)").unwrap();
+        assert!(matches!(Day8.part1(&network), Err(AOCError::NoSolution(_))));
+        assert!(matches!(Day8.part2(&network), Err(AOCError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        let network = Day8.parse("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert!(matches!(Day8.part1(&network), Err(AOCError::NoSolution(_))));
+
+        let network = Day8.parse("L\n\n11A = (11B, 11B)\n11B = (11B, 11B)").unwrap();
+        assert!(Day8.part2(&network).unwrap_err().to_string().contains("11A never reaches"));
+
+        let network = Day8.parse("L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22Z, 22Z)").unwrap();
+        assert!(Day8.part2(&network).unwrap_err().to_string().contains("never on nodes ending in 'Z' at the same time"));
+    }
+
+    /// Moves all ghosts one step at a time, only feasible on small networks.
+    fn brute_force(network: &Network) -> Option<u64> {
+        let mut states: Vec<State> = network.nodes.nodes()
+            .filter(|(_, name)| name.ends_with('A'))
+            .map(|(id, _)| (id, 0))
+            .collect();
+        for steps in 1..10_000 {
+            states = states.into_iter().map(|state| network.next_state(state)).collect();
+            if states.iter().all(|(id, _)| network.nodes.node(*id).ends_with('Z')) {
+                return Some(steps);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_part2_matches_brute_force() {
+        assert_eq!(ghost_steps(&input("tests/part2.txt")).ok(), brute_force(&input("tests/part2.txt")));
+
+        // On Z nodes at steps 1 (mod 4) and 3 (mod 6), which only meet at 9 (mod 12).
+        let network = Day8.parse("L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11C, 11C)\n11C = (11A, 11A)\n\
+            22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22D, 22D)\n22D = (22E, 22E)\n22E = (22A, 22A)").unwrap();
+        assert_eq!(ghost_steps(&network).unwrap(), 9);
+        assert_eq!(brute_force(&network), Some(9));
+
+        // Small networks from a fixed linear congruential generator, where
+        // ghosts take a while to enter their loops and pass several Z nodes.
+        // With two ghosts any meeting happens well within the brute force limit.
+        let mut state: u64 = 8;
+        let mut next = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % bound) as usize
+        };
+        for _ in 0..200 {
+            let directions: String = (0..1 + next(3)).map(|_| if next(2) == 0 {'L'} else {'R'}).collect();
+            let count = 4 + next(10);
+            let names: Vec<String> = (0..count)
+                .map(|i| format!("{i:02}{}", if i < 2 {'A'} else if next(2) == 0 {'Z'} else {'X'}))
+                .collect();
+            let mut network = format!("{directions}\n");
+            for name in &names {
+                network += &format!("\n{name} = ({}, {})", names[next(count as u64)], names[next(count as u64)]);
+            }
+            let parsed = Day8.parse(&network).unwrap();
+            assert_eq!(ghost_steps(&parsed).ok(), brute_force(&parsed), "{network}");
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day8::{ghost_steps, steps_to_zzz, Day8, Network};
+
+fn example(name: &str) -> Network {
+    Day8.parse_input(Path::new("tests").join(name)).unwrap()
+}
+
+#[test]
+fn test_network() {
+    let network = example("input2.txt");
+    assert_eq!(network.directions, vec!['L', 'L', 'R']);
+    assert_eq!(network.nodes.len(), 3);
+    let start = network.nodes.id("AAA").unwrap();
+    let (next, i) = network.next_state((start, 0));
+    assert_eq!((network.nodes.node(next).as_str(), i), ("BBB", 1));
+}
+
+#[test]
+fn test_part_functions() {
+    assert_eq!(steps_to_zzz(&example("input1.txt")).unwrap(), 2);
+    assert_eq!(steps_to_zzz(&example("input2.txt")).unwrap(), 6);
+    assert_eq!(ghost_steps(&example("part2.txt")).unwrap(), 6);
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day8::Day8);
@@ -0,0 +1,76 @@
+//! Day 9: Mirage Maintenance. Extrapolates sequences of readings forwards
+//! and backwards from their differences.
+
+use aoc::errors::AOCError;
+use aoc::Solution;
+
+#[derive(Default)]
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        input.lines().enumerate()
+            .map(|(i, line)| parse_values(line).map_err(|error| error.at_line(i + 1, line)))
+            .collect()
+    }
+
+    fn part1(&self, sequences: &Self::Input) -> Result<i64, AOCError> {
+        Ok(sum_of_next_values(sequences))
+    }
+
+    fn part2(&self, sequences: &Self::Input) -> Result<i64, AOCError> {
+        Ok(sum_of_previous_values(sequences))
+    }
+}
+
+/// The sum of the next value of every sequence.
+pub fn sum_of_next_values(sequences: &[Vec<i64>]) -> i64 {
+    sequences.iter().map(|values| predict_next_value(values)).sum()
+}
+
+/// The sum of the value before the first of every sequence.
+pub fn sum_of_previous_values(sequences: &[Vec<i64>]) -> i64 {
+    sequences.iter()
+        .map(|values| predict_next_value(&values.iter().rev().copied().collect::<Vec<i64>>()))
+        .sum()
+}
+
+/// The value after the last, from the differences between the values.
+pub fn predict_next_value(values: &[i64]) -> i64 {
+    if values.iter().all(|x| *x == 0i64) {
+        return 0;
+    }
+
+    let mut differences: Vec<i64> = Vec::new();
+    for window in values.windows(2) {
+        differences.push(window[1] - window[0]);
+    }
+
+    values.last().copied().unwrap_or(0) + predict_next_value(&differences)
+}
+
+/// The numbers on a line of readings.
+pub fn parse_values(line: &str) -> Result<Vec<i64>, AOCError> {
+    line.split_ascii_whitespace()
+        .map(|value| value.parse::<i64>()
+            .map_err(|_| AOCError::parse(format!("'{value}' is not a number.")).at_column_of(line, value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_history() {
+        let error = Day9.parse("0 3 6 9\n1 3 six 10").unwrap_err().to_string();
+        assert!(error.contains("'six' is not a number."));
+        assert!(error.contains(":2:5"));
+    }
+}
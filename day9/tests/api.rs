@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use aoc::Solution;
+use day9::{parse_values, predict_next_value, sum_of_next_values, sum_of_previous_values, Day9};
+
+#[test]
+fn test_part_functions() {
+    let readings = Day9.parse_input(Path::new("tests/input.txt")).unwrap();
+    assert_eq!(readings[0], vec![0, 3, 6, 9, 12, 15]);
+    assert_eq!(Day9.parse_input("0 3 6 9 12 15\r\n\r\n").unwrap(), vec![readings[0].clone()]);
+    assert_eq!(sum_of_next_values(&readings), 114);
+    assert_eq!(sum_of_previous_values(&readings), 2);
+}
+
+#[test]
+fn test_predict_next_value() {
+    let values = parse_values("10 13 16 21 30 45").unwrap();
+    assert_eq!(predict_next_value(&values), 68);
+    assert!(parse_values("10 x").is_err());
+}
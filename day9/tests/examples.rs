@@ -0,0 +1 @@
+aoc::example_tests!(day9::Day9);
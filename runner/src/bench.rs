@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc::errors::AOCError;
+use aoc::{read_input, Part};
+
+use crate::config::write_atomically;
+use crate::fetch::find_input;
+use crate::registry;
+use crate::run::{format_duration, parse_days, print_table};
+
+pub const DEFAULT_RUNS: usize = 20;
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+pub struct BenchOptions {
+    pub days: Vec<u32>,
+    pub runs: usize,
+    pub save: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    /// How many percent slower than the baseline counts as a regression.
+    pub threshold: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// `None` for no samples.
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let median = match count {
+            0 => return None,
+            _ if count % 2 == 1 => sorted[count / 2],
+            _ => (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        };
+        let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / count as f64;
+        let variance = seconds.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count as f64;
+        Some(Self { median, min: sorted[0], max: sorted[count - 1], stddev: Duration::from_secs_f64(variance.sqrt()) })
+    }
+}
+
+/// Median times from an earlier run, by day and stage.
+pub type Baseline = BTreeMap<(u32, String), Duration>;
+
+pub fn parse_args(args: &[String]) -> Result<BenchOptions, AOCError> {
+    let mut days: Vec<u32> = Vec::new();
+    let mut options = BenchOptions { days: Vec::new(), runs: DEFAULT_RUNS, save: None, compare: None, threshold: DEFAULT_THRESHOLD };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next()
+            .ok_or(AOCError::invalid_argument(format!("{name} needs a value")));
+        match arg.as_str() {
+            "--runs" => {
+                let runs = value("--runs")?;
+                options.runs = runs.parse().ok().filter(|runs| *runs > 0)
+                    .ok_or(AOCError::invalid_argument(format!("'{runs}' is not a positive number of runs")))?;
+            },
+            "--save" => options.save = Some(PathBuf::from(value("--save")?)),
+            "--compare" => options.compare = Some(PathBuf::from(value("--compare")?)),
+            "--threshold" => {
+                let threshold = value("--threshold")?;
+                options.threshold = threshold.trim_end_matches('%').parse().ok().filter(|threshold| *threshold >= 0.0)
+                    .ok_or(AOCError::invalid_argument(format!("'{threshold}' is not a percentage")))?;
+            },
+            flag if flag.starts_with("--") => {
+                return Err(AOCError::invalid_argument(format!("Unknown option '{flag}'")));
+            },
+            selection => days.append(&mut parse_days(selection)?)
+        }
+    }
+
+    if days.is_empty() {
+        days = registry::all();
+    }
+    days.sort();
+    days.dedup();
+    options.days = days;
+    Ok(options)
+}
+
+/// Solves both parts of `day` on `input` `runs` times and collects the
+/// timings of every stage.
+pub fn bench_day(day: u32, input: &str, runs: usize) -> Result<Vec<(&'static str, Stats)>, AOCError> {
+    let solution = registry::get(day)
+        .ok_or(AOCError::invalid_argument(format!("Day {day} is not implemented")))?;
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs {
+        let (answers, timings) = solution.solve_timed(input, &[Part::One, Part::Two])?;
+        if let Some(error) = answers.into_iter().find_map(Result::err) {
+            return Err(error);
+        }
+        samples[0].push(timings.parse);
+        for (stage, duration) in samples[1..].iter_mut().zip(timings.parts) {
+            stage.push(duration);
+        }
+    }
+    Ok(STAGES.into_iter()
+        .zip(&samples)
+        .filter_map(|(stage, samples)| Some((stage, Stats::of(samples)?)))
+        .collect())
+}
+
+pub fn load_baseline(path: &Path) -> Result<Baseline, AOCError> {
+    let contents = fs::read_to_string(path).map_err(|error| AOCError::io(path, error))?;
+    let mut baseline = Baseline::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let entry = match fields[..] {
+            [day, stage, median, ..] => day.parse::<u32>().ok()
+                .zip(median.parse::<u64>().ok())
+                .map(|(day, median)| ((day, stage.to_owned()), Duration::from_nanos(median))),
+            _ => None
+        };
+        let (key, median) = entry
+            .ok_or(AOCError::parse("Expected DAY, STAGE and MEDIAN_NS separated by tabs.").at_line(i + 1, line).in_file(path))?;
+        baseline.insert(key, median);
+    }
+    Ok(baseline)
+}
+
+pub fn baseline_file(results: &[(u32, &str, Stats)]) -> String {
+    let mut file = String::from("# day\tstage\tmedian_ns\tmin_ns\tmax_ns\tstddev_ns\n");
+    for (day, stage, stats) in results {
+        let _ = writeln!(
+            file, "{day}\t{stage}\t{}\t{}\t{}\t{}",
+            stats.median.as_nanos(), stats.min.as_nanos(), stats.max.as_nanos(), stats.stddev.as_nanos()
+        );
+    }
+    file
+}
+
+/// How much slower `median` is than `baseline`, in percent.
+pub fn change(median: Duration, baseline: Duration) -> f64 {
+    (median.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0
+}
+
+/// The file of the latest results with those of `results` replacing the
+/// earlier ones of the same days.
+pub fn merge_latest(latest: &str, results: &[(u32, &str, Stats)]) -> String {
+    let benched = |line: &str| line.split('\t').next()
+        .and_then(|day| day.parse::<u32>().ok())
+        .is_some_and(|day| results.iter().any(|(result, _, _)| *result == day));
+    let mut file = baseline_file(results);
+    for line in latest.lines().filter(|line| !line.starts_with('#') && !line.trim().is_empty() && !benched(line)) {
+        file += line;
+        file.push('\n');
+    }
+    file
+}
+
+/// Benchmarks the selected days and prints a table. Returns whether no
+/// stage got slower than the baseline by more than the threshold. The
+/// results are also kept in `latest` for `aoc status`.
+pub fn bench(options: &BenchOptions, latest: Option<&Path>) -> Result<bool, AOCError> {
+    let baseline = options.compare.as_deref().map(load_baseline).transpose()?;
+    let mut results: Vec<(u32, &str, Stats)> = Vec::new();
+    let mut regressions = 0;
+
+    let mut header = vec!["Day", "Stage", "Median", "Min", "Max", "Std dev"];
+    if baseline.is_some() {
+        header.extend(["Baseline", "Change"]);
+    }
+    let mut rows: Vec<Vec<String>> = vec![header.into_iter().map(String::from).collect()];
+
+    for &day in &options.days {
+        let Ok(path) = find_input(day) else {
+            eprintln!("Day {day}: no input, skipped");
+            continue;
+        };
+        let input = read_input(&path)?;
+        let stages = match bench_day(day, &input, options.runs) {
+            Ok(stages) => stages,
+            Err(error) => {
+                eprintln!("Day {day}: {}", error.in_file(&path));
+                regressions += 1;
+                continue;
+            }
+        };
+        for (stage, stats) in stages {
+            let mut row = vec![
+                day.to_string(), stage.to_owned(), format_duration(stats.median),
+                format_duration(stats.min), format_duration(stats.max), format_duration(stats.stddev)
+            ];
+            if let Some(baseline) = &baseline {
+                match baseline.get(&(day, stage.to_owned())) {
+                    Some(&before) => {
+                        let change = change(stats.median, before);
+                        let regressed = change > options.threshold;
+                        regressions += regressed as usize;
+                        row.push(format_duration(before));
+                        row.push(format!("{change:+.1}%{}", if regressed {" SLOWER"} else {""}));
+                    },
+                    None => row.extend([String::from("-"), String::from("new")])
+                }
+            }
+            rows.push(row);
+            results.push((day, stage, stats));
+        }
+    }
+
+    print_table(&rows, &[1]);
+    println!();
+    println!("{} runs per day", options.runs);
+
+    if let Some(path) = &options.save {
+        write_atomically(path, &baseline_file(&results))?;
+        println!("Saved the baseline to {}", path.display());
+    }
+    if let Some(path) = latest.filter(|_| !results.is_empty()) {
+        let before = fs::read_to_string(path).unwrap_or_default();
+        write_atomically(path, &merge_latest(&before, &results))?;
+    }
+    if regressions > 0 {
+        println!("{regressions} stages regressed or failed");
+    }
+    Ok(regressions == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|value| Duration::from_millis(*value)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::of(&millis(&[5, 1, 3, 9])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!((stats.min, stats.max), (Duration::from_millis(1), Duration::from_millis(9)));
+        assert!((stats.stddev.as_secs_f64() - 0.002958).abs() < 1e-5);
+        assert_eq!(Stats::of(&millis(&[7, 2, 3])).unwrap().median, Duration::from_millis(3));
+        assert_eq!(Stats::of(&[]), None);
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let input = read_input(Path::new("../day5/tests/input.txt")).unwrap();
+        let stages = bench_day(5, &input, 3).unwrap();
+        assert_eq!(stages.iter().map(|(stage, _)| *stage).collect::<Vec<_>>(), STAGES);
+        assert!(bench_day(5, "seeds: 1", 3).is_err());
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = Stats::of(&millis(&[2, 4])).unwrap();
+        let path = env::temp_dir().join(format!("aoc-baseline-{}.tsv", std::process::id()));
+        fs::write(&path, baseline_file(&[(5, "part2", stats), (11, "parse", stats)])).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.get(&(5, String::from("part2"))), Some(&Duration::from_millis(3)));
+        assert_eq!(baseline.len(), 2);
+
+        fs::write(&path, "5\tpart2\tslow\n").unwrap();
+        assert!(load_baseline(&path).unwrap_err().to_string().contains("MEDIAN_NS"));
+        fs::remove_file(&path).unwrap();
+
+        let latest = merge_latest(&baseline_file(&[(5, "parse", stats), (6, "parse", stats)]), &[(5, "part1", stats)]);
+        assert_eq!(latest.lines().filter(|line| line.starts_with("5\t")).count(), 1);
+        assert!(latest.contains("5\tpart1\t3000000\t") && latest.contains("6\tparse\t"));
+
+        assert_eq!(change(Duration::from_millis(11), Duration::from_millis(10)).round(), 10.0);
+        assert_eq!(change(Duration::from_millis(5), Duration::from_millis(10)).round(), -50.0);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let options = parse_args(&args("5,11 --runs 50 --compare base.tsv --threshold 5%")).unwrap();
+        assert_eq!(options.days, vec![5, 11]);
+        assert_eq!((options.runs, options.threshold), (50, 5.0));
+        assert_eq!(options.compare, Some(PathBuf::from("base.tsv")));
+        assert!(parse_args(&args("--runs 0")).is_err());
+        assert!(parse_args(&args("--threshold")).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use std::io;
+use std::time::Duration;
+
+use aoc::errors::AOCError;
+
+use crate::config::Config;
+
+const USER_AGENT: &str = concat!("aoc-2023-runner/", env!("CARGO_PKG_VERSION"));
+
+/// Authenticated requests to the Advent of Code website, or whatever
+/// stands in for it at the configured base URL.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, AOCError> {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+        Ok(Self { agent, base_url: config.base_url.clone(), session: config.session()?.to_owned() })
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, AOCError> {
+        let url = format!("{}{path}", self.base_url);
+        let request = self.agent.get(&url).set("Cookie", &format!("session={}", self.session));
+        read(&url, request.call())
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, AOCError> {
+        let url = format!("{}{path}", self.base_url);
+        let request = self.agent.post(&url).set("Cookie", &format!("session={}", self.session));
+        read(&url, request.send_form(fields))
+    }
+}
+
+fn read(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, AOCError> {
+    let failed = |message: String| AOCError::from(io::Error::other(format!("Request to {url} failed: {message}")));
+    match response {
+        Ok(response) => response.into_string().map_err(|error| failed(error.to_string())),
+        Err(ureq::Error::Status(400, _)) => Err(failed(String::from("400 Bad Request, is the session token still valid?"))),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(failed(format!("{code} {}", body.lines().next().unwrap_or("").trim())))
+        },
+        Err(error) => Err(failed(error.to_string()))
+    }
+}
@@ -0,0 +1,208 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc::errors::AOCError;
+use aoc::toml;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u32 = 2023;
+
+/// Settings for talking to the Advent of Code website. Every setting can
+/// come from an environment variable, which wins over the config file:
+///
+/// ```text
+/// # ~/.config/aoc/config.toml, or the file named by AOC_CONFIG
+/// session = "53616c74..."      # AOC_SESSION
+/// base_url = "https://..."     # AOC_BASE_URL
+/// year = 2023                  # AOC_YEAR
+/// cache_dir = "/tmp/aoc"       # AOC_CACHE_DIR
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u32,
+    pub cache_dir: PathBuf,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, AOCError> {
+        let lookup = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let path = lookup("AOC_CONFIG").map(PathBuf::from).or_else(|| default_config_path(&lookup));
+        let file = match &path {
+            Some(path) => match fs::read_to_string(path) {
+                Ok(contents) => Some(contents),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+                Err(error) => return Err(AOCError::io(path, error))
+            },
+            None => None
+        };
+        Self::from_sources(lookup, file.as_deref())
+            .map_err(|error| match &path {
+                Some(path) => error.in_file(path),
+                None => error
+            })
+    }
+
+    /// Builds the config from environment variables looked up with `lookup`
+    /// and the contents of the config file, if there is one.
+    pub fn from_sources<L>(lookup: L, file: Option<&str>) -> Result<Self, AOCError>
+    where
+        L: Fn(&str) -> Option<String>
+    {
+        let mut config = Self {
+            session: None,
+            base_url: String::from(DEFAULT_BASE_URL),
+            year: DEFAULT_YEAR,
+            cache_dir: PathBuf::new()
+        };
+        let mut cache_dir = None;
+
+        let mut settings: Vec<(String, String)> = match file {
+            Some(file) => parse_file(file)?,
+            None => Vec::new()
+        };
+        for key in ["session", "base_url", "year", "cache_dir"] {
+            if let Some(value) = lookup(&format!("AOC_{}", key.to_uppercase())) {
+                settings.push((String::from(key), value));
+            }
+        }
+
+        for (key, value) in settings {
+            match key.as_str() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value.trim_end_matches('/').to_owned(),
+                "year" => config.year = value.parse()
+                    .map_err(|_| AOCError::invalid_argument(format!("'{value}' is not a valid year")))?,
+                "cache_dir" => cache_dir = Some(PathBuf::from(value)),
+                _ => unreachable!("unknown keys are rejected while parsing")
+            }
+        }
+
+        config.cache_dir = cache_dir
+            .or_else(|| default_cache_dir(&lookup))
+            .ok_or(AOCError::invalid_argument("Could not find a cache directory, set AOC_CACHE_DIR"))?;
+        Ok(config)
+    }
+
+    /// Where the input for `day` is stored once fetched.
+    pub fn cached_input(&self, day: u32) -> PathBuf {
+        self.cache_dir.join(self.year.to_string()).join(format!("day{day}.txt"))
+    }
+
+    /// Every answer submitted for the configured year.
+    pub fn history_file(&self) -> PathBuf {
+        self.cache_dir.join(self.year.to_string()).join("history.tsv")
+    }
+
+    /// The medians of the latest `aoc bench` of every day.
+    pub fn bench_file(&self) -> PathBuf {
+        self.cache_dir.join(self.year.to_string()).join("bench.tsv")
+    }
+
+    pub fn session(&self) -> Result<&str, AOCError> {
+        self.session.as_deref()
+            .ok_or(AOCError::invalid_argument("No session token, set AOC_SESSION or 'session' in the config file"))
+    }
+}
+
+fn parse_file(file: &str) -> Result<Vec<(String, String)>, AOCError> {
+    let mut settings = Vec::new();
+    for section in toml::parse(file)? {
+        if let Some(header) = section.header {
+            return Err(header.error("Expected only settings, no sections."));
+        }
+        for entry in section.entries {
+            let key = entry.key.name;
+            if !["session", "base_url", "year", "cache_dir"].contains(&key) {
+                return Err(entry.key.error(format!("Unknown setting '{key}'.")));
+            }
+            settings.push((key.to_owned(), entry.value));
+        }
+    }
+    Ok(settings)
+}
+
+fn home<L: Fn(&str) -> Option<String>>(lookup: &L) -> Option<PathBuf> {
+    lookup("HOME").or_else(|| lookup("USERPROFILE")).map(PathBuf::from)
+}
+
+fn default_config_path<L: Fn(&str) -> Option<String>>(lookup: &L) -> Option<PathBuf> {
+    let base = lookup("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| lookup("APPDATA").map(PathBuf::from))
+        .or_else(|| home(lookup).map(|home| home.join(".config")))?;
+    Some(base.join("aoc").join("config.toml"))
+}
+
+fn default_cache_dir<L: Fn(&str) -> Option<String>>(lookup: &L) -> Option<PathBuf> {
+    let base = lookup("XDG_CACHE_HOME").map(PathBuf::from)
+        .or_else(|| lookup("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| home(lookup).map(|home| home.join(".cache")))?;
+    Some(base.join("aoc"))
+}
+
+/// Writes `contents` next to `path` first and renames it into place, so a
+/// failed write never leaves a truncated file behind.
+pub fn write_atomically(path: &Path, contents: &str) -> Result<(), AOCError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| AOCError::io(parent, error))?;
+    }
+    let temporary = path.with_extension("part");
+    fs::write(&temporary, contents).map_err(|error| AOCError::io(&temporary, error))?;
+    fs::rename(&temporary, path).map_err(|error| AOCError::io(path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn environment(variables: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let variables: HashMap<String, String> = variables.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| variables.get(name).cloned()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::from_sources(environment(&[("HOME", "/home/elf")]), None).unwrap();
+        assert_eq!(config.session, None);
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+        assert_eq!(config.cache_dir, PathBuf::from("/home/elf/.cache/aoc"));
+        assert_eq!(config.cached_input(5), PathBuf::from("/home/elf/.cache/aoc/2023/day5.txt"));
+        assert!(config.session().is_err());
+
+        let config = Config::from_sources(environment(&[("XDG_CACHE_HOME", "/cache")]), None).unwrap();
+        assert_eq!(config.cache_dir, PathBuf::from("/cache/aoc"));
+        assert!(Config::from_sources(environment(&[]), None).is_err());
+    }
+
+    #[test]
+    fn test_environment_wins_over_file() {
+        let file = "# Advent of Code\nsession = \"abc\"\nyear = 2022\n\nbase_url = http://localhost:8080/ # mock\n";
+        let variables = environment(&[("AOC_SESSION", "def"), ("AOC_CACHE_DIR", "/tmp/inputs")]);
+        let config = Config::from_sources(variables, Some(file)).unwrap();
+        assert_eq!(config.session.as_deref(), Some("def"));
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.cached_input(1), PathBuf::from("/tmp/inputs/2022/day1.txt"));
+    }
+
+    #[test]
+    fn test_malformed_file() {
+        let variables = || environment(&[("HOME", "/home/elf")]);
+        let message = |file: &str| Config::from_sources(variables(), Some(file)).unwrap_err().to_string();
+        assert!(message("session").contains("Expected 'key = value'."));
+        let error = message("\ntoken = abc");
+        assert!(error.contains("Unknown setting 'token'."));
+        assert!(error.contains(":2:1"));
+        assert!(message("year = next").contains("'next' is not a valid year"));
+        assert!(message("[aoc]\nyear = 2023").contains("no sections"));
+        let config = Config::from_sources(variables(), Some("session = \"ab#cd\" # token")).unwrap();
+        assert_eq!(config.session.as_deref(), Some("ab#cd"));
+    }
+}
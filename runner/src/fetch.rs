@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use aoc::errors::AOCError;
+
+use crate::client::Client;
+use crate::config::{write_atomically, Config};
+use crate::run::{local_input, parse_day};
+
+pub struct FetchOptions {
+    pub day: u32,
+    pub force: bool,
+}
+
+pub fn parse_args(args: &[String]) -> Result<FetchOptions, AOCError> {
+    let mut day = None;
+    let mut force = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--day needs a value"))?;
+                day = Some(parse_day(value)?);
+            },
+            "--force" => force = true,
+            other => return Err(AOCError::invalid_argument(format!("Unknown option '{other}'")))
+        }
+    }
+
+    let day = day.ok_or(AOCError::invalid_argument("--day is required"))?;
+    if !(1..=25).contains(&day) {
+        return Err(AOCError::invalid_argument(format!("Day {day} is not between 1 and 25")));
+    }
+    Ok(FetchOptions { day, force })
+}
+
+/// Downloads the input for the day into the cache unless it's already
+/// there, and returns where it is.
+pub fn fetch(config: &Config, options: &FetchOptions) -> Result<PathBuf, AOCError> {
+    let path = config.cached_input(options.day);
+    if path.is_file() && !options.force {
+        return Ok(path);
+    }
+
+    let client = Client::new(config)?;
+    let input = client.get(&format!("/{}/day/{}/input", config.year, options.day))?;
+    if input.trim().is_empty() {
+        return Err(AOCError::invalid_argument(format!("The input for day {} is empty", options.day)));
+    }
+    write_atomically(&path, &input)?;
+    Ok(path)
+}
+
+/// The input the runner uses when no `--input` is given: `dayN/input.txt`
+/// if it exists, otherwise the fetched input in the cache.
+pub fn find_input(day: u32) -> Result<PathBuf, AOCError> {
+    let local = local_input(day);
+    if local.is_file() {
+        return Ok(local);
+    }
+    let cached = Config::load()?.cached_input(day);
+    if cached.is_file() {
+        return Ok(cached);
+    }
+    Err(AOCError::invalid_argument(format!(
+        "No input for day {day}, expected {} or {}. Run 'aoc input fetch --day {day}' or pass --input.",
+        local.display(), cached.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use crate::mock::MockServer;
+
+    use super::*;
+
+    fn config(name: &str, server: &MockServer) -> Config {
+        let cache_dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        Config { session: Some(String::from("cookie")), base_url: server.url.clone(), year: 2023, cache_dir }
+    }
+
+    #[test]
+    fn test_fetch_caches_input() {
+        let server = MockServer::start(vec![(200, "1abc2\npqr3stu8vwx\n")]);
+        let config = config("cache", &server);
+        let options = FetchOptions { day: 1, force: false };
+
+        let path = fetch(&config, &options).unwrap();
+        assert_eq!(path, config.cached_input(1));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+        // The server only answers once, so this has to come from the cache.
+        assert_eq!(fetch(&config, &options).unwrap(), path);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2023/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=cookie"));
+        assert!(requests[0].header("User-Agent").is_some_and(|agent| agent.starts_with("aoc-2023-runner")));
+
+        assert!(fetch(&config, &FetchOptions { day: 1, force: true }).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1abc2\npqr3stu8vwx\n");
+        fs::remove_dir_all(&config.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let server = MockServer::start(vec![(404, "Please don't repeatedly request this endpoint before it unlocks!"), (400, "")]);
+        let config = config("errors", &server);
+        let options = FetchOptions { day: 25, force: false };
+
+        let error = fetch(&config, &options).unwrap_err().to_string();
+        assert!(error.contains("404 Please don't"), "{error}");
+        assert!(fetch(&config, &options).unwrap_err().to_string().contains("session token"));
+        assert!(!config.cached_input(25).exists());
+
+        let anonymous = Config { session: None, ..config };
+        assert!(fetch(&anonymous, &options).unwrap_err().to_string().contains("AOC_SESSION"));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let options = parse_args(&args("--day 7 --force")).unwrap();
+        assert_eq!((options.day, options.force), (7, true));
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("--day 26")).is_err());
+        assert!(parse_args(&args("--day 2 --part 1")).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{env, process};
+
+use aoc::errors::AOCError;
+use aoc::Format;
+use config::Config;
+
+mod bench;
+mod client;
+mod config;
+mod fetch;
+#[cfg(test)]
+mod mock;
+mod pool;
+mod registry;
+mod run;
+mod scaffold;
+mod status;
+mod submit;
+mod verify;
+mod watch;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("run") => match run::parse_args(&args[2..]) {
+            Ok(options) => {
+                let solved = match options.format {
+                    Format::Text => run::print_summary(&run::execute(&options)),
+                    Format::Json => run::print_json(&run::execute(&options), &options.parts),
+                    Format::Ndjson => run::stream_ndjson(&options)
+                };
+                if !solved {
+                    process::exit(1);
+                }
+            },
+            Err(error) => {
+                eprintln!("{error}");
+                exit_with_usage();
+            }
+        },
+        Some("input") if args.get(2).is_some_and(|command| command == "fetch") => {
+            let options = fetch::parse_args(&args[3..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            match Config::load().and_then(|config| fetch::fetch(&config, &options)) {
+                Ok(path) => println!("{}", path.display()),
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(1);
+                }
+            }
+        },
+        Some("submit") => {
+            let options = submit::parse_args(&args[2..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            let attempt = Config::load().and_then(|config| {
+                let answer = match &options.answer {
+                    Some(answer) => answer.clone(),
+                    None => submit::solve(options.day, options.part)?
+                };
+                println!("Submitting '{answer}' for day {} part {}", options.day, options.part.number());
+                submit::submit(&config, &options, &answer, submit::now())
+            });
+            match attempt {
+                Ok(attempt) => {
+                    println!("{}", attempt.outcome);
+                    if attempt.outcome != submit::Outcome::Correct {
+                        process::exit(1);
+                    }
+                },
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(1);
+                }
+            }
+        },
+        Some("verify") => {
+            let options = verify::parse_args(&args[2..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            match verify::verify(&options) {
+                Ok(true) => (),
+                Ok(false) => process::exit(1),
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(1);
+                }
+            }
+        },
+        Some("bench") => {
+            let options = bench::parse_args(&args[2..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            let latest = Config::load().ok().map(|config| config.bench_file());
+            match bench::bench(&options, latest.as_deref()) {
+                Ok(true) => (),
+                Ok(false) => process::exit(1),
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(1);
+                }
+            }
+        },
+        Some("new") => {
+            let day = scaffold::parse_args(&args[2..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            let written = env::current_dir()
+                .map_err(AOCError::from)
+                .and_then(|directory| scaffold::workspace_root(&directory))
+                .and_then(|root| scaffold::scaffold(&root, day));
+            match written {
+                Ok(paths) => {
+                    for path in paths {
+                        println!("Wrote {}", path.display());
+                    }
+                },
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(1);
+                }
+            }
+        },
+        Some("watch") => {
+            let options = watch::parse_args(&args[2..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            let watched = env::current_dir()
+                .map_err(AOCError::from)
+                .and_then(|directory| scaffold::workspace_root(&directory))
+                .and_then(|root| watch::watch(&root, &options));
+            if let Err(error) = watched {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        },
+        Some("status") => {
+            let mut options = status::parse_args(&args[2..]).unwrap_or_else(|error| {
+                eprintln!("{error}");
+                exit_with_usage()
+            });
+            if options.bench.is_none() {
+                options.bench = Config::load().ok().map(|config| config.bench_file());
+            }
+            let shown = env::current_dir()
+                .map_err(AOCError::from)
+                .and_then(|directory| scaffold::workspace_root(&directory))
+                .and_then(|root| status::status(&root, &options));
+            if let Err(error) = shown {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        },
+        _ => exit_with_usage()
+    }
+}
+
+fn exit_with_usage() -> ! {
+    println!("Usage: aoc run DAYS [--part 1|2|both] [--input INPUT_FILE] [--format text|json|ndjson] [--jobs N] [--timeout SECONDS]");
+    println!("       aoc run --all [--part 1|2|both] [--format text|json|ndjson] [--jobs N] [--timeout SECONDS]");
+    println!("       aoc input fetch --day N [--force]");
+    println!("       aoc submit --day N --part 1|2 [--answer ANSWER]");
+    println!("       aoc verify [DAYS] [--answers answers.toml] [--record]");
+    println!("       aoc new DAY");
+    println!("       aoc watch --day N [--part 1|2|both] [--input INPUT_FILE] [--answers answers.toml] [-- ARGS...]");
+    println!("       aoc status [--answers answers.toml] [--bench LATEST]");
+    println!("       aoc bench [DAYS] [--runs N] [--save BASELINE] [--compare BASELINE] [--threshold PERCENT]");
+    println!();
+    println!("DAYS is a day (5), an inclusive range (1..11) or a comma separated list (1,3,5).");
+    println!("Without --input, day N reads dayN/input.txt or the input fetched into the cache.");
+    println!("Fetching needs a session token in AOC_SESSION or ~/.config/aoc/config.toml.");
+    process::exit(1);
+}
@@ -0,0 +1,278 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::errors::AOCError;
+
+pub type Task<R> = Box<dyn FnOnce() -> R + Send>;
+
+/// What became of a task, see [`run_all`].
+type Outcome<R> = Result<R, AOCError>;
+
+/// The number of worker threads when `--jobs` isn't given.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+/// The tasks waiting for a worker, shared with the workers.
+struct Queue<R> {
+    tasks: VecDeque<(usize, Task<R>)>,
+    /// How many workers are held by a task that timed out.
+    stuck: usize,
+    /// Set when all tasks are done, so the idle workers stop.
+    closed: bool
+}
+
+type Shared<R> = Arc<(Mutex<Queue<R>>, Condvar)>;
+
+/// Runs `tasks` on `jobs` worker threads and passes every result to `done`
+/// with the index of its task, in the order they finish. A task that panics
+/// or runs longer than `timeout` fails with [`AOCError::Panicked`] or
+/// [`AOCError::TimedOut`], without affecting the others. Also passes on how
+/// long each task ran. The tasks `done` returns are run as well, numbered on
+/// from the last task so far.
+///
+/// Threads can't be stopped, so a task that timed out keeps running and keeps
+/// its worker until it finishes, and never more than `jobs` tasks run at once.
+/// Once every worker is held like that, the tasks still waiting fail with
+/// [`AOCError::NotStarted`] and the run returns without them.
+pub fn run_all<R: Send + 'static>(
+    tasks: Vec<Task<R>>,
+    jobs: usize,
+    timeout: Option<Duration>,
+    mut done: impl FnMut(usize, Result<R, AOCError>, Duration) -> Vec<Task<R>>
+) {
+    let mut count = tasks.len();
+    let workers = jobs.max(1);
+    let queue = Queue { tasks: tasks.into_iter().enumerate().collect(), stuck: 0, closed: false };
+    let shared: Shared<R> = Arc::new((Mutex::new(queue), Condvar::new()));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..workers {
+        let shared = Arc::clone(&shared);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            while let Some((index, task)) = next_task(&shared) {
+                let start = Instant::now();
+                let (result, abandoned) = supervise(task, timeout);
+                // Counted before the result is sent, so the receiver sees it.
+                if abandoned.is_some() {
+                    update(&shared, |queue| queue.stuck += 1);
+                }
+                if sender.send((index, result, start.elapsed())).is_err() {
+                    break;
+                }
+                if let Some(abandoned) = abandoned {
+                    let _ = abandoned.recv();
+                    update(&shared, |queue| queue.stuck -= 1);
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let mut finished = 0;
+    while finished < count {
+        // Workers stuck with a task that timed out may never hang up.
+        let Ok(received) = receiver.recv() else {
+            break;
+        };
+        let mut results = vec![received];
+        while let Some((index, result, elapsed)) = results.pop() {
+            finished += 1;
+            let more: Vec<_> = done(index, result, elapsed).into_iter()
+                .enumerate()
+                .map(|(i, task)| (count + i, task))
+                .collect();
+            count += more.len();
+            update(&shared, |queue| {
+                queue.tasks.extend(more);
+                if queue.stuck == workers {
+                    results.extend(queue.tasks.drain(..).map(|(index, _)| (index, Err(AOCError::NotStarted), Duration::ZERO)));
+                }
+            });
+        }
+    }
+    update(&shared, |queue| queue.closed = true);
+}
+
+/// Waits for a task to run, or `None` once all are done.
+fn next_task<R>(shared: &Shared<R>) -> Option<(usize, Task<R>)> {
+    let (queue, ready) = shared.as_ref();
+    let mut queue = queue.lock().ok()?;
+    loop {
+        if let Some(task) = queue.tasks.pop_front() {
+            return Some(task);
+        }
+        if queue.closed {
+            return None;
+        }
+        queue = ready.wait(queue).ok()?;
+    }
+}
+
+/// Changes the queue and wakes the workers waiting on it.
+fn update<R>(shared: &Shared<R>, change: impl FnOnce(&mut Queue<R>)) {
+    let (queue, ready) = shared.as_ref();
+    if let Ok(mut queue) = queue.lock() {
+        change(&mut queue);
+    }
+    ready.notify_all();
+}
+
+thread_local! {
+    /// Set while [`catch_panic`] runs, so the panic hook stays quiet.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` and turns a panic into [`AOCError::Panicked`], for callers which
+/// report panics on their own. Unlike with [`panic::catch_unwind`], the panic
+/// hook doesn't print the message and backtrace, on this thread only.
+pub fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, AOCError> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| if !CATCHING.get() {
+            hook(info);
+        }));
+    });
+
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| AOCError::panicked(payload.as_ref()));
+    CATCHING.set(catching);
+    result
+}
+
+/// Runs `task` on a thread of its own, so a task that never finishes can be
+/// left behind when it times out. Its result then arrives on the returned
+/// receiver, if ever.
+fn supervise<R: Send + 'static>(task: Task<R>, timeout: Option<Duration>) -> (Outcome<R>, Option<Receiver<Outcome<R>>>) {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(catch_panic(task));
+    });
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+    };
+    match result {
+        Ok(result) => (result, None),
+        Err(RecvTimeoutError::Timeout) => (Err(AOCError::TimedOut(timeout.unwrap_or_default())), Some(receiver)),
+        Err(RecvTimeoutError::Disconnected) => (Err(AOCError::Panicked(String::from("the task ended without a result"))), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_all() {
+        let tasks: Vec<Task<u32>> = vec![
+            Box::new(|| {
+                thread::sleep(Duration::from_millis(200));
+                1
+            }),
+            Box::new(|| panic!("day 2 is broken")),
+            Box::new(|| 3),
+            Box::new(|| loop {
+                thread::sleep(Duration::from_secs(1));
+            })
+        ];
+        let mut results: Vec<(usize, Result<u32, String>)> = Vec::new();
+        run_all(tasks, 2, Some(Duration::from_millis(500)), |index, result, _| {
+            results.push((index, result.map_err(|error| error.to_string())));
+            Vec::new()
+        });
+
+        // The quick tasks finish before the slow first one.
+        assert_eq!(results.iter().position(|(index, _)| *index == 2), Some(1));
+        results.sort_by_key(|(index, _)| *index);
+        assert_eq!(results[0].1, Ok(1));
+        assert_eq!(results[1].1, Err(String::from("Panicked: day 2 is broken")));
+        assert_eq!(results[2].1, Ok(3));
+        assert_eq!(results[3].1, Err(String::from("Timed out after 500ms")));
+    }
+
+    #[test]
+    fn test_timed_out_task_keeps_its_worker() {
+        let running = Arc::new(Mutex::new((0, 0)));
+        let task = |running: Arc<Mutex<(usize, usize)>>| Box::new(move || {
+            if let Ok(mut running) = running.lock() {
+                running.0 += 1;
+                running.1 = running.1.max(running.0);
+            }
+            thread::sleep(Duration::from_millis(150));
+            if let Ok(mut running) = running.lock() {
+                running.0 -= 1;
+            }
+        }) as Task<()>;
+        let tasks: Vec<Task<()>> = (0..3).map(|_| task(Arc::clone(&running))).collect();
+        let mut timeouts = 0;
+        run_all(tasks, 1, Some(Duration::from_millis(50)), |_, result, _| {
+            timeouts += result.is_err() as usize;
+            Vec::new()
+        });
+        assert_eq!(timeouts, 3);
+        assert_eq!(running.lock().unwrap().1, 1);
+    }
+
+    #[test]
+    fn test_never_ending_tasks_dont_hang_the_run() {
+        fn never_ending() -> Task<u32> {
+            Box::new(|| loop {
+                thread::sleep(Duration::from_secs(1));
+            })
+        }
+        let tasks: Vec<Task<u32>> = vec![never_ending(), never_ending(), Box::new(|| 3)];
+        let mut results: Vec<(usize, Result<u32, String>)> = Vec::new();
+        run_all(tasks, 1, Some(Duration::from_millis(50)), |index, result, _| {
+            results.push((index, result.map_err(|error| error.to_string())));
+            Vec::new()
+        });
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], (0, Err(String::from("Timed out after 50ms"))));
+        assert!(results[1..].iter().all(|(_, result)| result.as_ref().is_err_and(|error| error.starts_with("Not started"))));
+    }
+
+    #[test]
+    fn test_follow_up_tasks() {
+        fn value(value: u32) -> Task<u32> {
+            Box::new(move || value)
+        }
+        let mut results = Vec::new();
+        run_all(vec![value(1), value(2)], 2, None, |index, result, _| {
+            let value_of_task = result.unwrap();
+            results.push((index, value_of_task));
+            match value_of_task {
+                1 => vec![value(10), value(11)],
+                10 => vec![value(100)],
+                _ => Vec::new()
+            }
+        });
+        results.sort();
+        assert_eq!(results, vec![(0, 1), (1, 2), (2, 10), (3, 11), (4, 100)]);
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 1).unwrap(), 1);
+        let nested = catch_panic(|| catch_panic(|| panic!("inner")).map_err(|error| error.to_string()));
+        assert_eq!(nested.unwrap(), Err(String::from("Panicked: inner")));
+        assert!(catch_panic(|| panic!("outer")).is_err());
+        assert!(!CATCHING.get());
+    }
+
+    #[test]
+    fn test_run_all_without_tasks() {
+        let mut calls = 0;
+        run_all(Vec::<Task<()>>::new(), 4, None, |_, _, _| {
+            calls += 1;
+            Vec::new()
+        });
+        assert_eq!(calls, 0);
+    }
+}
@@ -0,0 +1,31 @@
+use aoc::{Runnable, Solution};
+
+pub type Constructor = fn() -> Box<dyn Runnable>;
+
+pub static DAYS: &[(u32, Constructor)] = &[
+    (1, boxed::<day1::Day1>),
+    (2, boxed::<day2::Day2>),
+    (3, boxed::<day3::Day3>),
+    (4, boxed::<day4::Day4>),
+    (5, boxed::<day5::Day5>),
+    (6, boxed::<day6::Day6>),
+    (7, boxed::<day7::Day7>),
+    (8, boxed::<day8::Day8>),
+    (9, boxed::<day9::Day9>),
+    (10, boxed::<day10::Day10>),
+    (11, boxed::<day11::Day11>),
+];
+
+fn boxed<S: Solution + 'static>() -> Box<dyn Runnable> {
+    Box::new(S::default())
+}
+
+pub fn get(day: u32) -> Option<Box<dyn Runnable>> {
+    DAYS.iter()
+        .find(|(number, _)| *number == day)
+        .map(|(_, constructor)| constructor())
+}
+
+pub fn all() -> Vec<u32> {
+    DAYS.iter().map(|(number, _)| *number).collect()
+}
@@ -0,0 +1,525 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aoc::errors::AOCError;
+use aoc::args::{parse_format, parse_parts};
+use aoc::json::{Json, PartReport};
+use aoc::{hash_input, read_input, Answer, Format, Part, SharedInput, Timings};
+
+use crate::fetch::find_input;
+use crate::pool::{self, Task};
+use crate::registry;
+
+type Answers = Vec<(Part, Answer)>;
+
+pub struct RunOptions {
+    pub days: Vec<u32>,
+    pub parts: Vec<Part>,
+    pub input: Option<PathBuf>,
+    pub format: Format,
+    /// How many inputs are parsed or parts solved at the same time.
+    pub jobs: usize,
+    /// How long parsing or a part may take before it is reported as failed.
+    pub timeout: Option<Duration>,
+}
+
+pub struct DayReport {
+    pub day: u32,
+    /// The input the day was run on, once it was found.
+    pub input: Option<PathBuf>,
+    pub input_hash: Option<String>,
+    pub answers: Result<Answers, AOCError>,
+    /// Missing when the day failed before its input was parsed.
+    pub timings: Option<Timings>,
+}
+
+impl DayReport {
+    fn failed(&self) -> bool {
+        match &self.answers {
+            Ok(answers) => answers.iter().any(|(_, answer)| answer.is_err()),
+            Err(_) => true
+        }
+    }
+
+    /// One JSON object per part in `parts`, the parts the day was run with.
+    pub fn to_json(&self, parts: &[Part]) -> Vec<Json> {
+        let solution = registry::get(self.day);
+        let input = self.input.as_ref().map(|path| path.display().to_string());
+        parts.iter().enumerate()
+            .map(|(i, &part)| {
+                let answer_type = solution.as_ref().map(|solution| solution.answer_type(part));
+                PartReport {
+                    day: self.day,
+                    part,
+                    answer: match &self.answers {
+                        Ok(answers) => answers[i].1.as_deref(),
+                        Err(error) => Err(error)
+                    },
+                    answer_type: answer_type.as_deref(),
+                    input: input.as_deref(),
+                    input_hash: self.input_hash.as_deref(),
+                    parse_time: self.timings.as_ref().map(|timings| timings.parse),
+                    part_time: self.timings.as_ref().and_then(|timings| timings.parts.get(i).copied())
+                }.to_json()
+            })
+            .collect()
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<RunOptions, AOCError> {
+    let mut days: Vec<u32> = Vec::new();
+    let mut all = false;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input: Option<PathBuf> = None;
+    let mut format = Format::Text;
+    let mut jobs = pool::default_jobs();
+    let mut timeout: Option<Duration> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--part" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--part needs a value: 1, 2 or both"))?;
+                parts = parse_parts(value)?;
+            },
+            "--input" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--input needs a path"))?;
+                input = Some(PathBuf::from(value));
+            },
+            "--format" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--format needs a value: text, json or ndjson"))?;
+                format = parse_format(value)?;
+            },
+            "--jobs" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--jobs needs a number of threads"))?;
+                jobs = value.parse().ok().filter(|jobs| *jobs > 0)
+                    .ok_or(AOCError::invalid_argument(format!("'{value}' is not a positive number of jobs")))?;
+            },
+            "--timeout" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--timeout needs a number of seconds"))?;
+                timeout = Some(value.parse().ok().filter(|seconds: &f64| *seconds > 0.0 && seconds.is_finite())
+                    .map(Duration::from_secs_f64)
+                    .ok_or(AOCError::invalid_argument(format!("'{value}' is not a positive number of seconds")))?);
+            },
+            flag if flag.starts_with("--") => {
+                return Err(AOCError::invalid_argument(format!("Unknown option '{flag}'")));
+            },
+            selection => days.append(&mut parse_days(selection)?)
+        }
+    }
+
+    if all {
+        if !days.is_empty() {
+            return Err(AOCError::invalid_argument("--all can't be combined with a list of days"));
+        }
+        days = registry::all();
+    }
+    if days.is_empty() {
+        return Err(AOCError::invalid_argument("No days selected"));
+    }
+    if input.is_some() && days.len() != 1 {
+        return Err(AOCError::invalid_argument("--input can only be used when running a single day"));
+    }
+    days.sort();
+    days.dedup();
+
+    Ok(RunOptions { days, parts, input, format, jobs, timeout })
+}
+
+pub fn parse_days(selection: &str) -> Result<Vec<u32>, AOCError> {
+    let mut days: Vec<u32> = Vec::new();
+    for item in selection.split(',') {
+        let (first, last) = match item.split_once("..") {
+            Some((first, last)) => (parse_day(first)?, parse_day(last.trim_start_matches('='))?),
+            None => {
+                let day = parse_day(item)?;
+                (day, day)
+            }
+        };
+        if first > last {
+            return Err(AOCError::invalid_argument(format!("'{item}' is an empty range of days")));
+        }
+        days.extend(first..=last);
+    }
+    Ok(days)
+}
+
+pub fn parse_day(value: &str) -> Result<u32, AOCError> {
+    value.trim().parse::<u32>()
+        .map_err(|_| AOCError::invalid_argument(format!("'{value}' is not a valid day")))
+}
+
+pub fn execute(options: &RunOptions) -> Vec<DayReport> {
+    let mut reports = Vec::new();
+    execute_in_order(options, |report| reports.push(report));
+    reports
+}
+
+/// What a task of [`execute_in_order`] comes back with.
+enum Step {
+    /// The report of a day so far, and its input unless that couldn't be parsed.
+    Parsed(DayReport, Option<SharedInput>),
+    /// The answer to the part at this position of the day's report, and how long it took.
+    Solved(usize, Answer, Duration)
+}
+
+/// Solves every day on the thread pool and passes it to `done` as soon as it
+/// and all days before it are finished, so a slow day holds back the output
+/// of later days but not their work. A day's input is read and parsed once,
+/// then its parts are solved at the same time, each as a task of its own that
+/// can time out or panic without the other.
+pub fn execute_in_order(options: &RunOptions, mut done: impl FnMut(DayReport)) {
+    let tasks: Vec<Task<Step>> = options.days.iter()
+        .map(|&day| {
+            let parts = options.parts.clone();
+            let input = options.input.clone();
+            Box::new(move || parse_input(day, &parts, input.as_deref())) as Task<Step>
+        })
+        .collect();
+
+    // The day of every task, and the part unless it parses the input.
+    let mut owners: Vec<(usize, Option<usize>)> = (0..options.days.len()).map(|index| (index, None)).collect();
+    let mut reports: Vec<Option<DayReport>> = options.days.iter().map(|_| None).collect();
+    let mut unsolved: Vec<usize> = vec![0; options.days.len()];
+    let mut next = 0;
+    pool::run_all(tasks, options.jobs, options.timeout, |task, result, elapsed| {
+        let (index, part) = owners[task];
+        let day = options.days[index];
+        let mut solve = Vec::new();
+        let solved = match (result, part) {
+            (Ok(Step::Parsed(report, input)), _) => {
+                if let Some(input) = input {
+                    for (i, &part) in options.parts.iter().enumerate() {
+                        let input = Arc::clone(&input);
+                        owners.push((index, Some(i)));
+                        solve.push(Box::new(move || solve_part(day, &input, i, part)) as Task<Step>);
+                    }
+                }
+                unsolved[index] = solve.len();
+                reports[index] = Some(report);
+                None
+            },
+            (Ok(Step::Solved(i, answer, time)), _) => Some((i, answer, time)),
+            (Err(error), Some(i)) => Some((i, Err(error), elapsed)),
+            (Err(error), None) => {
+                reports[index] = Some(DayReport { day, input: None, input_hash: None, answers: Err(error), timings: None });
+                None
+            }
+        };
+        if let Some((i, answer, time)) = solved {
+            if let Some(report) = &mut reports[index] {
+                if let Ok(answers) = &mut report.answers {
+                    answers[i].1 = answer;
+                }
+                if let Some(timings) = &mut report.timings {
+                    timings.parts[i] = time;
+                }
+            }
+            unsolved[index] -= 1;
+        }
+
+        while next < reports.len() && unsolved[next] == 0 {
+            let Some(report) = reports[next].take() else {
+                break;
+            };
+            done(report);
+            next += 1;
+        }
+        solve
+    });
+}
+
+/// Reads and parses the input of `day` for solving `parts` on it. Keeps what
+/// is learned on the way in the report even when a later step fails.
+fn parse_input(day: u32, parts: &[Part], input: Option<&Path>) -> Step {
+    let mut report = DayReport { day, input: None, input_hash: None, answers: Ok(Vec::new()), timings: None };
+    match read_and_parse(&mut report, input) {
+        Ok((parsed, parse)) => {
+            report.answers = Ok(parts.iter().map(|&part| (part, Err(AOCError::NotStarted))).collect());
+            report.timings = Some(Timings { parse, parts: vec![Duration::ZERO; parts.len()] });
+            Step::Parsed(report, Some(parsed))
+        },
+        Err(error) => {
+            report.answers = Err(error);
+            Step::Parsed(report, None)
+        }
+    }
+}
+
+fn read_and_parse(report: &mut DayReport, input: Option<&Path>) -> Result<(SharedInput, Duration), AOCError> {
+    let day = report.day;
+    let solution = registry::get(day)
+        .ok_or(AOCError::invalid_argument(format!("Day {day} is not implemented")))?;
+    let path = match input {
+        Some(path) => path.to_path_buf(),
+        None => find_input(day)?
+    };
+    let path = report.input.insert(path);
+    let input = read_input(path)?;
+    report.input_hash = Some(hash_input(&input));
+    let start = Instant::now();
+    let parsed = solution.parse_shared(&input).map_err(|error| error.in_file(path))?;
+    Ok((parsed, start.elapsed()))
+}
+
+fn solve_part(day: u32, input: &SharedInput, i: usize, part: Part) -> Step {
+    let Some(solution) = registry::get(day) else {
+        return Step::Solved(i, Err(AOCError::invalid_argument(format!("Day {day} is not implemented"))), Duration::ZERO);
+    };
+    let start = Instant::now();
+    let answer = solution.solve_part(input, part);
+    Step::Solved(i, answer, start.elapsed())
+}
+
+/// Prints the results of all days as one JSON array. Returns whether every part was solved.
+pub fn print_json(reports: &[DayReport], parts: &[Part]) -> bool {
+    let values = reports.iter().flat_map(|report| report.to_json(parts)).collect();
+    println!("{}", Json::Array(values));
+    !reports.iter().any(DayReport::failed)
+}
+
+/// Prints a line of JSON per part as soon as its day and all days before it
+/// are done. Returns whether every part was solved.
+pub fn stream_ndjson(options: &RunOptions) -> bool {
+    let mut solved = true;
+    execute_in_order(options, |report| {
+        for value in report.to_json(&options.parts) {
+            println!("{value}");
+        }
+        solved &= !report.failed();
+    });
+    solved
+}
+
+/// `dayN/input.txt` in the workspace, wherever the runner is started.
+pub fn local_input(day: u32) -> PathBuf {
+    aoc::workspace_dir().join(format!("day{day}")).join("input.txt")
+}
+
+/// Renders a duration with three significant digits, like `12.3 ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = [(1e9, "s"), (1e6, "ms"), (1e3, "µs")].into_iter()
+        .find(|(scale, _)| nanos >= *scale)
+        .map_or((nanos, "ns"), |(scale, unit)| (nanos / scale, unit));
+    let decimals = if value >= 100.0 {0} else if value >= 10.0 {1} else {2};
+    format!("{value:.decimals$} {unit}")
+}
+
+/// Prints `rows` as a table with the first row as the header. Columns are
+/// right aligned unless listed in `left_aligned`.
+pub fn print_table<R: AsRef<[String]>>(rows: &[R], left_aligned: &[usize]) {
+    let columns = rows.first().map_or(0, |row| row.as_ref().len());
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| rows.iter().map(|row| row.as_ref()[column].chars().count()).max().unwrap_or(0))
+        .collect();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row.as_ref().iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, &width))| if left_aligned.contains(&column) {
+                format!("{cell:<width$}")
+            } else {
+                format!("{cell:>width$}")
+            })
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+        if i == 0 {
+            let rules: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            println!("{}", rules.join("-+-"));
+        }
+    }
+}
+
+pub fn print_summary(reports: &[DayReport]) -> bool {
+    let mut rows: Vec<[String; 6]> = vec![
+        ["Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2"].map(String::from)
+    ];
+    for report in reports {
+        let mut row = [report.day.to_string(), String::from("-"), String::from("-"), String::new(), String::new(), String::new()];
+        match &report.answers {
+            Ok(answers) => {
+                for (part, answer) in answers {
+                    let column = part.number() as usize;
+                    row[column] = match answer {
+                        Ok(answer) => answer.clone(),
+                        Err(_) => String::from("failed")
+                    };
+                }
+            },
+            Err(_) => {
+                row[1] = String::from("failed");
+                row[2] = String::from("failed");
+            }
+        }
+        if let (Ok(answers), Some(timings)) = (&report.answers, &report.timings) {
+            row[3] = format_duration(timings.parse);
+            for ((part, _), duration) in answers.iter().zip(&timings.parts) {
+                row[3 + part.number() as usize] = format_duration(*duration);
+            }
+        }
+        rows.push(row);
+    }
+
+    print_table(&rows, &[1, 2]);
+
+    let failures: Vec<&DayReport> = reports.iter().filter(|report| report.failed()).collect();
+    if !failures.is_empty() {
+        println!();
+        for report in &failures {
+            match &report.answers {
+                Ok(answers) => {
+                    for (part, answer) in answers {
+                        if let Err(error) = answer {
+                            let number = part.number();
+                            eprintln!("Day {} part {number}: {error}", report.day);
+                        }
+                    }
+                },
+                Err(error) => eprintln!("Day {}: {error}", report.day)
+            }
+        }
+    }
+    failures.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("5").unwrap(), vec![5]);
+        assert_eq!(parse_days("1..4").unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(parse_days("1..=2,7").unwrap(), vec![1, 2, 7]);
+        assert!(parse_days("4..2").is_err());
+        assert!(parse_days("five").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args("5 --part 2 --input day5/tests/input.txt --format ndjson --jobs 3 --timeout 1.5")).unwrap();
+        assert_eq!(options.days, vec![5]);
+        assert_eq!(options.parts, vec![Part::Two]);
+        assert_eq!(options.format, Format::Ndjson);
+        assert_eq!((options.jobs, options.timeout), (3, Some(Duration::from_millis(1500))));
+        assert_eq!(options.input, Some(PathBuf::from("day5/tests/input.txt")));
+    }
+
+    #[test]
+    fn test_parse_args_all() {
+        let options = parse_args(&args("--all")).unwrap();
+        assert_eq!(options.days, registry::all());
+        assert_eq!(options.parts, vec![Part::One, Part::Two]);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("1 --all")).is_err());
+        assert!(parse_args(&args("1..3 --input input.txt")).is_err());
+        assert!(parse_args(&args("1 --part 3")).is_err());
+        assert!(parse_args(&args("1 --verbose")).is_err());
+        assert!(parse_args(&args("1 --format yaml")).is_err());
+        assert!(parse_args(&args("1 --jobs 0")).is_err());
+        assert!(parse_args(&args("1 --timeout -1")).is_err());
+    }
+
+    #[test]
+    fn test_local_input() {
+        let workspace = env::current_dir().unwrap().parent().unwrap().to_path_buf();
+        assert_eq!(local_input(5), workspace.join("day5").join("input.txt"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(80)), "80.0 ns");
+        assert_eq!(format_duration(Duration::from_nanos(7_294)), "7.29 µs");
+        assert_eq!(format_duration(Duration::from_micros(123_456)), "123 ms");
+        assert_eq!(format_duration(Duration::from_millis(12_345)), "12.3 s");
+    }
+
+    #[test]
+    fn test_execute_keeps_going_after_failure() {
+        let options = RunOptions {
+            days: vec![5, 42],
+            parts: vec![Part::One, Part::Two],
+            input: Some(PathBuf::from("../day5/tests/input.txt")),
+            format: Format::Text,
+            jobs: 4,
+            timeout: None
+        };
+        let reports = execute(&options);
+        let answers = reports[0].answers.as_ref().unwrap();
+        assert_eq!(answers[0].1.as_ref().unwrap(), "35");
+        assert_eq!(answers[1].1.as_ref().unwrap(), "46");
+        assert_eq!(reports[0].timings.as_ref().map(|timings| timings.parts.len()), Some(2));
+        assert!(reports[1].answers.is_err());
+        assert!(reports[1].timings.is_none());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let options = RunOptions {
+            days: vec![5, 42],
+            parts: vec![Part::Two],
+            input: Some(PathBuf::from("../day5/tests/input.txt")),
+            format: Format::Json,
+            jobs: 2,
+            timeout: None
+        };
+        let reports = execute(&options);
+        let solved = reports[0].to_json(&options.parts)[0].to_string();
+        assert!(solved.starts_with(r#"{"day":5,"part":2,"answer":"46","answer_type":"i64","input":"../day5/tests/input.txt","input_hash":"#));
+        assert!(solved.ends_with(r#""error":null}"#));
+
+        let missing = reports[1].to_json(&options.parts)[0].to_string();
+        assert_eq!(
+            missing,
+            r#"{"day":42,"part":2,"answer":null,"answer_type":null,"input":null,"input_hash":null,"timings":{"parse_ns":null,"part_ns":null},"error":{"kind":"invalid_argument","message":"Day 42 is not implemented"}}"#
+        );
+    }
+
+    #[test]
+    fn test_execute_timeout() {
+        let options = RunOptions {
+            days: vec![5],
+            parts: vec![Part::One, Part::Two],
+            input: Some(PathBuf::from("../day5/tests/input.txt")),
+            format: Format::Text,
+            jobs: 1,
+            timeout: Some(Duration::from_nanos(1))
+        };
+        let reports = execute(&options);
+        assert!(matches!(reports[0].answers, Err(AOCError::TimedOut(_))));
+        assert!(reports[0].timings.is_none());
+    }
+
+    #[test]
+    fn test_execute_in_order() {
+        let options = RunOptions {
+            days: vec![2, 5, 9, 42],
+            parts: vec![Part::One, Part::Two],
+            input: None,
+            format: Format::Ndjson,
+            jobs: 3,
+            timeout: Some(Duration::from_secs(30))
+        };
+        let mut days = Vec::new();
+        execute_in_order(&options, |report| days.push(report.day));
+        assert_eq!(days, vec![2, 5, 9, 42]);
+    }
+}
@@ -0,0 +1,298 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc::errors::AOCError;
+
+use crate::config::write_atomically;
+use crate::run::parse_day;
+
+const CARGO_TOML: &str = r#"[package]
+name = "day{N}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc = { path = "../aoc" }
+
+[[test]]
+name = "examples"
+harness = false
+"#;
+
+const MAIN_RS: &str = r#"fn main() {
+    aoc::run::<day{N}::Day{N}>();
+}
+"#;
+
+const LIB_RS: &str = r#"//! Day {N}: the puzzle's title. What the input is and what the parts ask for.
+
+use aoc::errors::AOCError;
+use aoc::Solution;
+
+#[derive(Default)]
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    const DAY: u32 = {N};
+
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AOCError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, _lines: &Self::Input) -> Result<u64, AOCError> {
+        Err(AOCError::no_solution("part 1 is not solved yet"))
+    }
+
+    fn part2(&self, _lines: &Self::Input) -> Result<u64, AOCError> {
+        Err(AOCError::no_solution("part 2 is not solved yet"))
+    }
+}
+"#;
+
+const EXAMPLES_RS: &str = r#"aoc::example_tests!(day{N}::Day{N});
+"#;
+
+const EXAMPLE_TOML: &str = r#"# The answers to the example in input.txt, checked by `cargo test`.
+# part1 = 0
+# part2 = 0
+"#;
+
+const LAUNCH_ENTRY: &str = r#"        {
+            "type": "lldb",
+            "request": "launch",
+            "name": "Debug executable 'day{N}'",
+            "cargo": {
+                "args": [
+                    "build",
+                    "--bin=day{N}",
+                    "--package=day{N}"
+                ],
+                "filter": {
+                    "name": "day{N}",
+                    "kind": "bin"
+                }
+            },
+            "args": [
+                "--example",
+                "1"
+            ],
+            "cwd": "${workspaceFolder}"
+        }"#;
+
+pub fn parse_args(args: &[String]) -> Result<u32, AOCError> {
+    let [day] = args else {
+        return Err(AOCError::invalid_argument("Expected exactly one day"));
+    };
+    let day = parse_day(day)?;
+    if !(1..=25).contains(&day) {
+        return Err(AOCError::invalid_argument(format!("Day {day} is not between 1 and 25")));
+    }
+    Ok(day)
+}
+
+/// The closest directory from `start` upwards whose `Cargo.toml` declares the workspace.
+pub fn workspace_root(start: &Path) -> Result<PathBuf, AOCError> {
+    start.ancestors()
+        .find(|directory| fs::read_to_string(directory.join("Cargo.toml"))
+            .is_ok_and(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]")))
+        .map(Path::to_path_buf)
+        .ok_or(AOCError::invalid_argument("Not inside the workspace, no Cargo.toml with [workspace] found"))
+}
+
+/// Creates `dayN` from the template and registers it with the workspace,
+/// the runner and the debugger. Returns the files written.
+pub fn scaffold(root: &Path, day: u32) -> Result<Vec<PathBuf>, AOCError> {
+    let name = format!("day{day}");
+    let crate_dir = root.join(&name);
+    if crate_dir.exists() {
+        return Err(AOCError::invalid_argument(format!("{} already exists", crate_dir.display())));
+    }
+
+    // Work out every edit before writing anything, so a file that doesn't
+    // look as expected leaves the workspace untouched.
+    let read = |path: &Path| fs::read_to_string(path).map_err(|error| AOCError::io(path, error));
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("runner").join("Cargo.toml");
+    let registry = root.join("runner").join("src").join("registry.rs");
+    let launch = root.join(".vscode").join("launch.json");
+
+    let mut edits = vec![
+        (workspace.clone(), add_member(&read(&workspace)?, day).map_err(|error| error.in_file(&workspace))?),
+        (runner.clone(), add_dependency(&read(&runner)?, day).map_err(|error| error.in_file(&runner))?),
+        (registry.clone(), add_to_registry(&read(&registry)?, day).map_err(|error| error.in_file(&registry))?)
+    ];
+    if launch.is_file() {
+        edits.push((launch.clone(), add_launch_entry(&read(&launch)?, day).map_err(|error| error.in_file(&launch))?));
+    }
+
+    let fill = |template: &str| template.replace("{N}", &day.to_string());
+    edits.extend([
+        (crate_dir.join("Cargo.toml"), fill(CARGO_TOML)),
+        (crate_dir.join("src").join("main.rs"), fill(MAIN_RS)),
+        (crate_dir.join("src").join("lib.rs"), fill(LIB_RS)),
+        (crate_dir.join("tests").join("examples.rs"), fill(EXAMPLES_RS)),
+        (crate_dir.join("tests").join("input.txt"), String::new()),
+        (crate_dir.join("tests").join("input.toml"), String::from(EXAMPLE_TOML))
+    ]);
+
+    for (path, contents) in &edits {
+        write_atomically(path, contents)?;
+    }
+    Ok(edits.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Adds `"dayN",` to the workspace members, in order of the days.
+fn add_member(manifest: &str, day: u32) -> Result<String, AOCError> {
+    let quoted = format!("\"day{day}\"");
+    if manifest.lines().any(|line| line.trim().trim_end_matches(',') == quoted) {
+        return Err(AOCError::invalid_argument(format!("day{day} is already a workspace member")));
+    }
+    let lines: Vec<&str> = manifest.lines().collect();
+    let index = day_position(&lines, day, |line| {
+        line.trim().trim_end_matches(',').trim_matches('"').strip_prefix("day")?.parse().ok()
+    }).ok_or(AOCError::parse("Expected the days in the workspace members."))?;
+    let neighbour = lines[index.min(lines.len() - 1)];
+    let indent = &neighbour[..neighbour.len() - neighbour.trim_start().len()];
+    Ok(insert_line(&lines, index, &format!("{indent}{quoted},")))
+}
+
+/// Adds `dayN = { path = "../dayN" }` among the runner's other day dependencies.
+fn add_dependency(manifest: &str, day: u32) -> Result<String, AOCError> {
+    let name = format!("day{day}");
+    let lines: Vec<&str> = manifest.lines().collect();
+    if lines.iter().any(|line| line.starts_with(&format!("{name} "))) {
+        return Err(AOCError::invalid_argument(format!("The runner already depends on {name}")));
+    }
+    let index = day_position(&lines, day, |line| {
+        line.contains("path").then_some(())?;
+        line.split_whitespace().next()?.strip_prefix("day")?.parse().ok()
+    }).ok_or(AOCError::parse("Expected the days in the runner's dependencies."))?;
+    Ok(insert_line(&lines, index, &format!("{name} = {{ path = \"../{name}\" }}")))
+}
+
+fn add_to_registry(registry: &str, day: u32) -> Result<String, AOCError> {
+    let lines: Vec<&str> = registry.lines().collect();
+    let index = day_position(&lines, day, |line| {
+        line.contains("boxed::<day").then_some(())?;
+        line.trim().strip_prefix('(')?.split(',').next()?.trim().parse().ok()
+    }).ok_or(AOCError::parse("Expected the days in the DAYS table."))?;
+    Ok(insert_line(&lines, index, &format!("    ({day}, boxed::<day{day}::Day{day}>),")))
+}
+
+/// Where the line of `day` goes among the lines `day_of` finds a day in:
+/// after the last earlier day, or before the first day if there is none.
+fn day_position<F>(lines: &[&str], day: u32, day_of: F) -> Option<usize>
+where
+    F: Fn(&str) -> Option<u32>
+{
+    let days: Vec<(usize, u32)> = lines.iter().enumerate()
+        .filter_map(|(i, line)| Some((i, day_of(line)?)))
+        .collect();
+    let (first, _) = days.first()?;
+    Some(days.iter().rev().find(|(_, other)| *other < day).map_or(*first, |(i, _)| i + 1))
+}
+
+/// Appends a debug configuration to the end of the `configurations` list.
+fn add_launch_entry(launch: &str, day: u32) -> Result<String, AOCError> {
+    let end = launch.rfind(']')
+        .ok_or(AOCError::parse("Expected a 'configurations' list."))?;
+    let before = launch[..end].trim_end();
+    let separator = if before.ends_with('[') {""} else {","};
+    let entry = LAUNCH_ENTRY.replace("{N}", &day.to_string());
+    Ok(format!("{before}{separator}\n{entry}\n    {}", &launch[end..]))
+}
+
+fn insert_line(lines: &[&str], index: usize, line: &str) -> String {
+    let mut lines = lines.to_vec();
+    lines.insert(index, line);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// A copy of the files `aoc new` edits, in a fresh directory.
+    fn workspace(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-new-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in ["Cargo.toml", "runner/Cargo.toml", "runner/src/registry.rs", ".vscode/launch.json"] {
+            let target = root.join(file);
+            fs::create_dir_all(target.parent().unwrap()).unwrap();
+            fs::copy(Path::new("..").join(file), target).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = workspace("scaffold");
+        let written = scaffold(&root, 12).unwrap();
+        assert_eq!(written.len(), 10);
+
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        assert!(read("Cargo.toml").contains("    \"day11\",\n    \"day12\",\n    \"runner\""));
+        assert!(read("runner/Cargo.toml").contains("day11 = { path = \"../day11\" }\nday12 = { path = \"../day12\" }\n"));
+        assert!(read("runner/src/registry.rs").contains("    (12, boxed::<day12::Day12>),\n];"));
+        assert!(read(".vscode/launch.json").contains("        },\n        {\n            \"type\": \"lldb\""));
+        assert!(read(".vscode/launch.json").ends_with("\"cwd\": \"${workspaceFolder}\"\n        }\n    ]\n}"));
+        assert!(read("day12/src/lib.rs").starts_with("//! Day 12: "));
+        assert!(read("day12/src/lib.rs").contains("impl Solution for Day12 {\n    const DAY: u32 = 12;"));
+        assert!(read("day12/src/main.rs").contains("aoc::run::<day12::Day12>();"));
+        assert!(read("day12/Cargo.toml").ends_with("[[test]]\nname = \"examples\"\nharness = false\n"));
+        assert!(read("day12/tests/examples.rs").contains("aoc::example_tests!(day12::Day12);"));
+        assert!(root.join("day12/tests/input.txt").is_file());
+        assert!(read("day12/tests/input.toml").contains("# part1 = 0"));
+
+        let error = scaffold(&root, 12).unwrap_err().to_string();
+        assert!(error.contains("day12 already exists"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_refuses_existing_day() {
+        let root = workspace("existing");
+        assert!(scaffold(&root, 11).unwrap_err().to_string().contains("already a workspace member"));
+        assert!(!root.join("day11").exists());
+        assert_eq!(fs::read_to_string(root.join("Cargo.toml")).unwrap(), fs::read_to_string("../Cargo.toml").unwrap());
+
+        fs::create_dir(root.join("day5")).unwrap();
+        assert!(scaffold(&root, 5).unwrap_err().to_string().contains("day5 already exists"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_days_stay_in_order() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day4\",\n    \"day10\",\n    \"runner\"\n]\n";
+        assert!(add_member(manifest, 5).unwrap().contains("\"day4\",\n    \"day5\",\n    \"day10\","));
+        assert!(add_member(manifest, 12).unwrap().contains("\"day10\",\n    \"day12\",\n    \"runner\""));
+
+        let dependencies = "[dependencies]\naoc = { path = \"../aoc\" }\nday2 = { path = \"../day2\" }\nday3 = { path = \"../day3\" }\n";
+        assert!(add_dependency(dependencies, 1).unwrap().contains("aoc = { path = \"../aoc\" }\nday1 = { path = \"../day1\" }\nday2 "));
+
+        let registry = "pub static DAYS: &[(u32, Constructor)] = &[\n    (4, boxed::<day4::Day4>),\n    (6, boxed::<day6::Day6>),\n];\n";
+        assert!(add_to_registry(registry, 5).unwrap().contains("(4, boxed::<day4::Day4>),\n    (5, boxed::<day5::Day5>),\n    (6,"));
+        assert!(add_member("[workspace]\nmembers = []\n", 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&[String::from("12")]).unwrap(), 12);
+        assert!(parse_args(&[String::from("26")]).is_err());
+        assert!(parse_args(&[]).is_err());
+    }
+
+    #[test]
+    fn test_workspace_root() {
+        let root = workspace_root(&env::current_dir().unwrap()).unwrap();
+        assert!(root.join("runner").is_dir());
+    }
+}
@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc::errors::AOCError;
+use aoc::examples::find_examples;
+use aoc::{read_input, Part, Runnable};
+
+use crate::bench::{load_baseline, Baseline};
+use crate::pool::catch_panic;
+use crate::registry;
+use crate::run::{format_duration, print_table};
+use crate::verify::{default_answers, KnownAnswers};
+
+pub struct StatusOptions {
+    pub answers: PathBuf,
+    /// The latest benchmark results, the cached ones of `aoc bench` by default.
+    pub bench: Option<PathBuf>,
+}
+
+/// Where a day stands, from what is on disk.
+#[derive(Debug, PartialEq)]
+pub struct DayStatus {
+    pub day: u32,
+    /// Whether a part does more than fail with "no solution" on the first example.
+    pub implemented: [bool; 2],
+    /// Whether the answers file knows the answer to a part.
+    pub verified: [bool; 2],
+    /// The latest benchmark medians of parsing and both parts.
+    pub timings: [Option<Duration>; 3],
+    /// How many of the examples with answers pass, out of how many.
+    pub examples: Result<(usize, usize), String>,
+}
+
+impl DayStatus {
+    pub fn stars(&self) -> usize {
+        self.verified.iter().filter(|verified| **verified).count()
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<StatusOptions, AOCError> {
+    let mut options = StatusOptions { answers: default_answers(), bench: None };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next()
+            .ok_or(AOCError::invalid_argument(format!("{name} needs a value")));
+        match arg.as_str() {
+            "--answers" => options.answers = PathBuf::from(value("--answers")?),
+            "--bench" => options.bench = Some(PathBuf::from(value("--bench")?)),
+            other => return Err(AOCError::invalid_argument(format!("Unknown option '{other}'")))
+        }
+    }
+    Ok(options)
+}
+
+/// Looks at a day's examples, known answers and latest benchmark. Only the
+/// examples are solved, never the real input.
+pub fn day_status(root: &Path, day: u32, known: &KnownAnswers, latest: &Baseline) -> DayStatus {
+    let solution = registry::get(day);
+    let examples = find_examples(&root.join(format!("day{day}")).join("tests"));
+    let parts = [Part::One, Part::Two];
+
+    let implemented = match (&solution, &examples) {
+        (Some(solution), Ok(examples)) => {
+            let input = examples.first().and_then(|example| read_input(&example.path).ok()).unwrap_or_default();
+            implemented(solution.as_ref(), &input)
+        },
+        (Some(solution), Err(_)) => implemented(solution.as_ref(), ""),
+        (None, _) => Ok([false; 2])
+    };
+    let examples = match (&solution, examples, &implemented) {
+        (_, _, Err(error)) => Err(format!("parse error: {error}")),
+        (Some(solution), Ok(examples), _) => {
+            let passed = examples.iter()
+                .filter(|example| catch_panic(|| solution.check_example(example))
+                    .is_ok_and(|result| result.is_ok()))
+                .count();
+            Ok((passed, examples.len()))
+        },
+        (None, _, _) => Err(String::from("not registered")),
+        (_, Err(error), _) => Err(error.to_string())
+    };
+
+    let stage = |name: &str| latest.get(&(day, name.to_owned())).copied();
+    DayStatus {
+        day,
+        implemented: implemented.unwrap_or_default(),
+        verified: parts.map(|part| known.has_answer(day, part)),
+        timings: [stage("parse"), stage("part1"), stage("part2")],
+        examples
+    }
+}
+
+/// A part that fails with [`AOCError::NoSolution`] or panics on `input` is
+/// taken to be a stub. Fails with the error of a parser rejecting `input`.
+fn implemented(solution: &dyn Runnable, input: &str) -> Result<[bool; 2], String> {
+    let answers = catch_panic(|| solution.solve(input, &[Part::One, Part::Two]));
+    match answers {
+        Ok(Ok(answers)) => Ok([0, 1].map(|i| !matches!(answers[i], Err(AOCError::NoSolution(_))))),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(_) => Ok([false; 2])
+    }
+}
+
+/// The 25 days in rows of five, with a `*` for every verified part and a
+/// `+` for every part that is solved but not verified yet.
+pub fn calendar(statuses: &[DayStatus]) -> String {
+    let mut calendar = String::new();
+    for week in 0..5 {
+        let cells: Vec<String> = (1..=5).map(|weekday| {
+            let day = week * 5 + weekday;
+            let marks: String = statuses.iter()
+                .find(|status| status.day == day)
+                .map(|status| (0..2).map(|i| match (status.verified[i], status.implemented[i]) {
+                    (true, _) => '*',
+                    (false, true) => '+',
+                    (false, false) => '.'
+                }).collect())
+                .unwrap_or_default();
+            format!("{day:>2} {marks:<2}")
+        }).collect();
+        calendar += cells.join("  ").trim_end();
+        calendar.push('\n');
+    }
+    calendar
+}
+
+fn part_cell(status: &DayStatus, i: usize) -> String {
+    String::from(match (status.verified[i], status.implemented[i]) {
+        (true, _) => "verified",
+        (false, true) => "solved",
+        (false, false) => "-"
+    })
+}
+
+/// Prints a line per registered day and the calendar.
+pub fn status(root: &Path, options: &StatusOptions) -> Result<(), AOCError> {
+    let known = KnownAnswers::load(&options.answers)?;
+    let latest = match &options.bench {
+        Some(path) if path.is_file() => load_baseline(path)?,
+        _ => Baseline::new()
+    };
+    let statuses: Vec<DayStatus> = registry::all().into_iter()
+        .map(|day| day_status(root, day, &known, &latest))
+        .collect();
+
+    let mut rows: Vec<[String; 7]> = vec![
+        ["Day", "Part 1", "Part 2", "Examples", "Parse", "Time 1", "Time 2"].map(String::from)
+    ];
+    for status in &statuses {
+        let examples = match status.examples {
+            Ok((passed, total)) if passed == total => format!("{passed}/{total}"),
+            Ok((passed, total)) => format!("{passed}/{total} FAILING"),
+            Err(ref error) => error.lines().next().unwrap_or_default().to_owned()
+        };
+        let [parse, part1, part2] = status.timings.map(|timing| timing.map_or(String::from("-"), format_duration));
+        rows.push([status.day.to_string(), part_cell(status, 0), part_cell(status, 1), examples, parse, part1, part2]);
+    }
+    print_table(&rows, &[1, 2, 3]);
+    println!();
+    print!("{}", calendar(&statuses));
+    println!();
+    println!("{} of 50 stars", statuses.iter().map(DayStatus::stars).sum::<usize>());
+    if latest.is_empty() {
+        println!("No benchmark results yet, run 'aoc bench' for timings.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_status() {
+        let known = KnownAnswers::parse("[day5.0123456789abcdef]\npart1 = \"35\"\n").unwrap();
+        let latest = Baseline::from([((5, String::from("part2")), Duration::from_micros(60))]);
+        let status = day_status(Path::new(".."), 5, &known, &latest);
+        assert_eq!(status, DayStatus {
+            day: 5,
+            implemented: [true, true],
+            verified: [true, false],
+            timings: [None, None, Some(Duration::from_micros(60))],
+            examples: Ok((1, 1))
+        });
+        assert_eq!(status.stars(), 1);
+        assert_eq!(day_status(Path::new(".."), 25, &known, &latest).examples, Err(String::from("not registered")));
+    }
+
+    #[test]
+    fn test_implemented() {
+        let day5 = registry::get(5).unwrap();
+        let example = read_input(Path::new("../day5/tests/input.txt")).unwrap();
+        assert_eq!(implemented(day5.as_ref(), &example), Ok([true, true]));
+        assert!(implemented(day5.as_ref(), "seeds: x").unwrap_err().contains("seeds"));
+    }
+
+    #[test]
+    fn test_calendar() {
+        let status = |day: u32, verified: [bool; 2], implemented: [bool; 2]| DayStatus {
+            day, implemented, verified, timings: [None; 3], examples: Ok((0, 0))
+        };
+        let calendar = calendar(&[
+            status(1, [true, true], [true, true]),
+            status(2, [true, false], [true, true]),
+            status(3, [false, false], [true, false]),
+        ]);
+        let lines: Vec<&str> = calendar.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], " 1 **   2 *+   3 +.   4      5");
+        assert_eq!(lines[4], "21     22     23     24     25");
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let options = parse_args(&args("--answers mine.toml --bench latest.tsv")).unwrap();
+        assert_eq!(options.answers, PathBuf::from("mine.toml"));
+        assert_eq!(options.bench, Some(PathBuf::from("latest.tsv")));
+        assert!(parse_args(&args("--verbose")).is_err());
+    }
+}
@@ -0,0 +1,417 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aoc::args::parse_parts;
+use aoc::errors::AOCError;
+use aoc::{read_input, Part};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::fetch::find_input;
+use crate::registry;
+use crate::run::parse_day;
+
+/// How long the site makes you wait after a wrong answer when it doesn't say.
+const DEFAULT_COOLDOWN: u64 = 60;
+
+pub struct SubmitOptions {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Option<String>,
+}
+
+/// What the site said about an answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the last answer, nothing was checked.
+    Wait,
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown
+}
+
+impl Outcome {
+    const NAMES: [(Outcome, &'static str); 7] = [
+        (Outcome::Correct, "correct"),
+        (Outcome::TooHigh, "too-high"),
+        (Outcome::TooLow, "too-low"),
+        (Outcome::Wrong, "wrong"),
+        (Outcome::Wait, "wait"),
+        (Outcome::WrongLevel, "wrong-level"),
+        (Outcome::Unknown, "unknown")
+    ];
+
+    fn name(self) -> &'static str {
+        Self::NAMES.iter().find(|(outcome, _)| *outcome == self).map_or("unknown", |(_, name)| name)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES.iter().find(|(_, other)| *other == name).map(|(outcome, _)| *outcome)
+    }
+
+    /// Whether the answer itself was checked and rejected.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            Outcome::Correct => "That's the right answer!",
+            Outcome::TooHigh => "That's not the right answer, it's too high.",
+            Outcome::TooLow => "That's not the right answer, it's too low.",
+            Outcome::Wrong => "That's not the right answer.",
+            Outcome::Wait => "You gave an answer too recently.",
+            Outcome::WrongLevel => "You don't seem to be solving the right level.",
+            Outcome::Unknown => "The response was not understood."
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// One submitted answer, stored as a tab separated line in the history file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+    /// Seconds to wait before the next answer.
+    pub cooldown: u64,
+    pub answer: String,
+}
+
+impl Attempt {
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}\t{}", self.time, self.day, self.part, self.outcome.name(), self.cooldown, self.answer)
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(6, '\t');
+        Some(Self {
+            time: fields.next()?.parse().ok()?,
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            outcome: Outcome::from_name(fields.next()?)?,
+            cooldown: fields.next()?.parse().ok()?,
+            answer: fields.next()?.to_owned()
+        })
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<SubmitOptions, AOCError> {
+    let mut day = None;
+    let mut part = None;
+    let mut answer = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--day needs a value"))?;
+                day = Some(parse_day(value)?);
+            },
+            "--part" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--part needs a value: 1 or 2"))?;
+                match parse_parts(value)?[..] {
+                    [single] => part = Some(single),
+                    _ => return Err(AOCError::invalid_argument("Only one part can be submitted at a time"))
+                }
+            },
+            "--answer" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--answer needs a value"))?;
+                check_characters(value)?;
+                answer = Some(value.clone());
+            },
+            other => return Err(AOCError::invalid_argument(format!("Unknown option '{other}'")))
+        }
+    }
+
+    let day = day.ok_or(AOCError::invalid_argument("--day is required"))?;
+    let part = part.ok_or(AOCError::invalid_argument("--part is required"))?;
+    Ok(SubmitOptions { day, part, answer })
+}
+
+/// Runs the day's solution on its input to get the answer to submit.
+pub fn solve(day: u32, part: Part) -> Result<String, AOCError> {
+    let solution = registry::get(day)
+        .ok_or(AOCError::invalid_argument(format!("Day {day} is not implemented")))?;
+    let path = find_input(day)?;
+    let input = read_input(&path)?;
+    let answers = solution.solve(&input, &[part]).map_err(|error| error.in_file(&path))?;
+    answers.into_iter().next()
+        .unwrap_or(Err(AOCError::no_solution("the solution returned no answer")))
+}
+
+/// Checks `answer` against the history, posts it and records the outcome.
+pub fn submit(config: &Config, options: &SubmitOptions, answer: &str, now: u64) -> Result<Attempt, AOCError> {
+    let path = config.history_file();
+    let history = load_history(&path)?;
+    check(&history, options.day, options.part.number(), answer, now)?;
+
+    let client = Client::new(config)?;
+    let level = options.part.number().to_string();
+    let response = client.post_form(
+        &format!("/{}/day/{}/answer", config.year, options.day),
+        &[("level", &level), ("answer", answer)]
+    )?;
+    let (outcome, cooldown) = parse_response(&response);
+
+    let attempt = Attempt { time: now, day: options.day, part: options.part.number(), outcome, cooldown, answer: answer.to_owned() };
+    append_history(&path, &attempt)?;
+    Ok(attempt)
+}
+
+/// Tabs and line breaks would split the answer's line in the history.
+fn check_characters(answer: &str) -> Result<(), AOCError> {
+    match answer.chars().find(|c| c.is_control()) {
+        Some(c) => Err(AOCError::invalid_argument(format!("Not submitting {answer:?}: it contains the control character {c:?}"))),
+        None => Ok(())
+    }
+}
+
+/// Refuses answers which can't be right going by earlier attempts, answers
+/// sent before the site's cooldown is over and answers with control characters.
+pub fn check(history: &[Attempt], day: u32, part: u32, answer: &str, now: u64) -> Result<(), AOCError> {
+    check_characters(answer)?;
+    let attempts: Vec<&Attempt> = history.iter()
+        .filter(|attempt| attempt.day == day && attempt.part == part)
+        .collect();
+    let refuse = |reason: String| Err(AOCError::invalid_argument(format!("Not submitting '{answer}': {reason}")));
+
+    if let Some(correct) = attempts.iter().find(|attempt| attempt.outcome == Outcome::Correct) {
+        return refuse(format!("part {part} of day {day} was already solved with '{}'", correct.answer));
+    }
+    if let Some(wrong) = attempts.iter().find(|attempt| attempt.outcome.is_wrong() && attempt.answer == answer) {
+        return refuse(format!("it was already rejected. {}", wrong.outcome));
+    }
+    if let Ok(value) = answer.parse::<i128>() {
+        let bound = |outcome: Outcome| attempts.iter()
+            .filter(move |attempt| attempt.outcome == outcome)
+            .filter_map(|attempt| attempt.answer.parse::<i128>().ok());
+        if let Some(high) = bound(Outcome::TooHigh).min().filter(|high| value >= *high) {
+            return refuse(format!("{high} was already too high"));
+        }
+        if let Some(low) = bound(Outcome::TooLow).max().filter(|low| value <= *low) {
+            return refuse(format!("{low} was already too low"));
+        }
+    }
+    // Only a wrong answer or being told to wait starts a cooldown, other
+    // outcomes are recorded with none and leave a running one alone.
+    let cooldowns = history.iter()
+        .filter(|attempt| attempt.day == day && (attempt.outcome.is_wrong() || attempt.outcome == Outcome::Wait));
+    if let Some(last) = cooldowns.max_by_key(|attempt| attempt.time) {
+        let ready = last.time + last.cooldown;
+        if ready > now {
+            return refuse(format!("wait {} more before the next answer", format_duration(ready - now)));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the outcome and the cooldown it imposes from the page the site
+/// answers with.
+pub fn parse_response(page: &str) -> (Outcome, u64) {
+    let text = page.to_lowercase();
+    if text.contains("that's the right answer") {
+        return (Outcome::Correct, 0);
+    }
+    if text.contains("you gave an answer too recently") {
+        return (Outcome::Wait, left_to_wait(&text).unwrap_or(DEFAULT_COOLDOWN));
+    }
+    if text.contains("that's not the right answer") {
+        let outcome = if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        };
+        return (outcome, please_wait(&text).unwrap_or(DEFAULT_COOLDOWN));
+    }
+    if text.contains("you don't seem to be solving the right level") {
+        return (Outcome::WrongLevel, 0);
+    }
+    (Outcome::Unknown, 0)
+}
+
+/// Reads `you have 1m 30s left to wait`.
+fn left_to_wait(text: &str) -> Option<u64> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("you have ")? + "you have ".len();
+    text[start..end].split_whitespace()
+        .map(|part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None
+            }
+        })
+        .sum()
+}
+
+/// Reads `please wait one minute` or `please wait 5 minutes`.
+fn please_wait(text: &str) -> Option<u64> {
+    let start = text.find("please wait ")? + "please wait ".len();
+    let mut words = text[start..].split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        number => number.parse().ok()?
+    };
+    words.next()?.starts_with("minute").then_some(minutes * 60)
+}
+
+fn format_duration(seconds: u64) -> String {
+    match (seconds / 60, seconds % 60) {
+        (0, seconds) => format!("{seconds}s"),
+        (minutes, seconds) => format!("{minutes}m {seconds}s")
+    }
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<Attempt>, AOCError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => contents.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| Attempt::from_line(line)
+                .ok_or_else(|| AOCError::parse("Expected TIME, DAY, PART, OUTCOME, COOLDOWN and ANSWER separated by tabs.")
+                    .at_line(i + 1, line)
+                    .in_file(path)))
+            .collect(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(AOCError::io(path, error))
+    }
+}
+
+fn append_history(path: &Path, attempt: &Attempt) -> Result<(), AOCError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| AOCError::io(parent, error))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", attempt.to_line()))
+        .map_err(|error| AOCError::io(path, error))
+}
+
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::mock::MockServer;
+
+    use super::*;
+
+    const RIGHT: &str = "<main><article><p>That's the right answer! You are one gold star closer to restoring snow operations.</p></article></main>";
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>. Please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>";
+    const TOO_RECENT: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 23s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>";
+
+    fn attempt(time: u64, outcome: Outcome, answer: &str) -> Attempt {
+        Attempt { time, day: 1, part: 1, outcome, cooldown: if outcome.is_wrong() {60} else {0}, answer: answer.to_owned() }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(RIGHT), (Outcome::Correct, 0));
+        assert_eq!(parse_response(TOO_HIGH), (Outcome::TooHigh, 60));
+        assert_eq!(parse_response(TOO_RECENT), (Outcome::Wait, 263));
+        assert_eq!(parse_response("That's not the right answer; your answer is too low. Please wait 5 minutes before"), (Outcome::TooLow, 300));
+        assert_eq!(parse_response("That's not the right answer. Please wait one minute"), (Outcome::Wrong, 60));
+        assert_eq!(parse_response("You don't seem to be solving the right level.  Did you already complete it?").0, Outcome::WrongLevel);
+        assert_eq!(parse_response("<html>Puzzle inputs differ by user.</html>").0, Outcome::Unknown);
+    }
+
+    #[test]
+    fn test_history_lines() {
+        let attempt = Attempt { time: 1701406800, day: 5, part: 2, outcome: Outcome::TooLow, cooldown: 60, answer: String::from("a b\tc") };
+        assert_eq!(Attempt::from_line(&attempt.to_line()), Some(attempt));
+        assert_eq!(Attempt::from_line("1701406800\t5\t2\tnope\t0\t42"), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let history = [
+            attempt(100, Outcome::TooHigh, "500"),
+            attempt(200, Outcome::TooLow, "100"),
+            attempt(300, Outcome::Wrong, "abc"),
+            attempt(400, Outcome::TooHigh, "300")
+        ];
+        let message = |answer: &str| check(&history, 1, 1, answer, 1000).unwrap_err().to_string();
+        assert!(message("abc").contains("already rejected"));
+        assert!(message("300").contains("already rejected. That's not the right answer, it's too high."));
+        assert!(message("400").contains("300 was already too high"));
+        assert!(message("99").contains("100 was already too low"));
+        assert!(message("-5").contains("100 was already too low"));
+        assert!(check(&history, 1, 1, "200", 1000).is_ok());
+        assert!(check(&history, 1, 2, "300", 1000).is_ok());
+
+        assert!(check(&history, 1, 1, "200", 430).unwrap_err().to_string().contains("wait 30s more"));
+        let mut after_wrong = history.to_vec();
+        after_wrong.push(Attempt { part: 2, ..attempt(410, Outcome::WrongLevel, "7") });
+        after_wrong.push(attempt(420, Outcome::Unknown, "8"));
+        assert!(check(&after_wrong, 1, 1, "200", 430).unwrap_err().to_string().contains("wait 30s more"));
+        after_wrong.push(Attempt { cooldown: 100, ..attempt(425, Outcome::Wait, "9") });
+        assert!(check(&after_wrong, 1, 1, "200", 500).unwrap_err().to_string().contains("wait 25s more"));
+        let solved = [attempt(100, Outcome::Correct, "250")];
+        assert!(check(&solved, 1, 1, "250", 1000).unwrap_err().to_string().contains("already solved with '250'"));
+    }
+
+    #[test]
+    fn test_submit_records_history() {
+        let server = MockServer::start(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let cache_dir = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let config = Config { session: Some(String::from("cookie")), base_url: server.url.clone(), year: 2023, cache_dir };
+        let options = SubmitOptions { day: 1, part: Part::Two, answer: None };
+
+        let attempt = submit(&config, &options, "281", 1000).unwrap();
+        assert_eq!((attempt.outcome, attempt.cooldown), (Outcome::TooHigh, 60));
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/1/answer");
+        assert_eq!(requests[0].body, "level=2&answer=281");
+
+        // Refused from the history alone, the server is never asked.
+        assert!(submit(&config, &options, "281", 2000).is_err());
+        assert!(submit(&config, &options, "300", 2000).is_err());
+        assert!(submit(&config, &options, "142", 1030).unwrap_err().to_string().contains("wait 30s"));
+        assert_eq!(server.requests().len(), 1);
+
+        assert_eq!(submit(&config, &options, "142", 2000).unwrap().outcome, Outcome::Correct);
+        let history = load_history(&config.history_file()).unwrap();
+        assert_eq!(history.iter().map(|attempt| attempt.answer.as_str()).collect::<Vec<_>>(), vec!["281", "142"]);
+        fs::remove_dir_all(&config.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let options = parse_args(&args("--day 3 --part 2 --answer 467835")).unwrap();
+        assert_eq!((options.day, options.part, options.answer.as_deref()), (3, Part::Two, Some("467835")));
+        assert!(parse_args(&args("--day 3")).is_err());
+        assert!(parse_args(&args("--day 3 --part both")).is_err());
+        let tab = vec![String::from("--day"), String::from("3"), String::from("--part"), String::from("1"), String::from("--answer"), String::from("4\t2")];
+        assert!(parse_args(&tab).is_err_and(|error| error.to_string().contains("'\\t'")));
+        assert!(check(&[], 3, 1, "12\n34", 0).unwrap_err().to_string().contains("'\\n'"));
+    }
+}
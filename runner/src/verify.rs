@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc::errors::AOCError;
+use aoc::toml;
+use aoc::{hash_input, read_input, Part};
+
+use crate::config::write_atomically;
+use crate::fetch::find_input;
+use crate::registry;
+use crate::run::parse_days;
+
+/// `answers.toml` at the top of the workspace, wherever the runner is started.
+pub fn default_answers() -> PathBuf {
+    aoc::workspace_dir().join("answers.toml")
+}
+
+pub struct VerifyOptions {
+    pub days: Vec<u32>,
+    pub answers: PathBuf,
+    /// Adds the answers of missing entries to the file.
+    pub record: bool,
+}
+
+/// The answers we know are right for real inputs, told apart by a hash of
+/// the input so the file never contains the inputs themselves:
+///
+/// ```toml
+/// [day5.9b3f2c0d1e4a5b6c]
+/// input = "day5/input.txt"
+/// part1 = "35"
+/// part2 = "46"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KnownAnswers {
+    inputs: BTreeMap<(u32, String), Entry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Entry {
+    /// Where the input was read from when it was recorded, for people reading the file.
+    input: Option<String>,
+    answers: [Option<String>; 2],
+}
+
+impl KnownAnswers {
+    pub fn load(path: &Path) -> Result<Self, AOCError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents).map_err(|error| error.in_file(path)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(AOCError::io(path, error))
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, AOCError> {
+        let mut known = Self::default();
+        for section in toml::parse(contents)? {
+            let key = match section.header {
+                Some(header) => {
+                    let key = header.name.strip_prefix("day")
+                        .and_then(|name| name.split_once('.'))
+                        .and_then(|(day, hash)| Some((day.parse::<u32>().ok()?, hash.trim_matches('"').to_owned())))
+                        .ok_or(header.error("Expected a section like '[day5.9b3f2c0d1e4a5b6c]'."))?;
+                    known.inputs.entry(key.clone()).or_default();
+                    Some(key)
+                },
+                None => None
+            };
+            for setting in section.entries {
+                let entry = key.as_ref()
+                    .and_then(|key| known.inputs.get_mut(key))
+                    .ok_or(setting.key.error("Expected a '[dayN.HASH]' section first."))?;
+                if !setting.quoted {
+                    return Err(setting.value_error("Expected a quoted value."));
+                }
+                match setting.key.name {
+                    "input" => entry.input = Some(setting.value),
+                    "part1" => entry.answers[0] = Some(setting.value),
+                    "part2" => entry.answers[1] = Some(setting.value),
+                    key => return Err(setting.key.error(format!("Unknown key '{key}', expected input, part1 or part2.")))
+                }
+            }
+        }
+        Ok(known)
+    }
+
+    pub fn get(&self, day: u32, hash: &str, part: Part) -> Option<&str> {
+        self.inputs.get(&(day, hash.to_owned()))
+            .and_then(|entry| entry.answers[part.number() as usize - 1].as_deref())
+    }
+
+    /// Whether an answer to `part` of `day` is known for any input.
+    pub fn has_answer(&self, day: u32, part: Part) -> bool {
+        self.inputs.iter()
+            .any(|((known_day, _), entry)| *known_day == day && entry.answers[part.number() as usize - 1].is_some())
+    }
+
+    pub fn insert(&mut self, day: u32, hash: &str, input: &Path, part: Part, answer: &str) {
+        let entry = self.inputs.entry((day, hash.to_owned())).or_default();
+        entry.input.get_or_insert_with(|| input.display().to_string());
+        entry.answers[part.number() as usize - 1] = Some(answer.to_owned());
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = String::from("# Known answers for `aoc verify`, keyed by day and input hash.\n");
+        for ((day, hash), entry) in &self.inputs {
+            let _ = write!(text, "\n[day{day}.{hash}]\n");
+            if let Some(input) = &entry.input {
+                let _ = writeln!(text, "input = {}", toml::quote(input));
+            }
+            for (part, answer) in entry.answers.iter().enumerate() {
+                if let Some(answer) = answer {
+                    let _ = writeln!(text, "part{} = {}", part + 1, toml::quote(answer));
+                }
+            }
+        }
+        text
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Match,
+    Mismatch { expected: String },
+    /// Nothing is known about this input yet.
+    Missing,
+    Failed(String),
+}
+
+pub struct Check {
+    pub part: Part,
+    pub answer: Option<String>,
+    pub status: Status,
+}
+
+pub fn parse_args(args: &[String]) -> Result<VerifyOptions, AOCError> {
+    let mut days: Vec<u32> = Vec::new();
+    let mut answers = default_answers();
+    let mut record = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let value = args.next()
+                    .ok_or(AOCError::invalid_argument("--answers needs a path"))?;
+                answers = PathBuf::from(value);
+            },
+            "--record" => record = true,
+            flag if flag.starts_with("--") => {
+                return Err(AOCError::invalid_argument(format!("Unknown option '{flag}'")));
+            },
+            selection => days.append(&mut parse_days(selection)?)
+        }
+    }
+
+    if days.is_empty() {
+        days = registry::all();
+    }
+    days.sort();
+    days.dedup();
+    Ok(VerifyOptions { days, answers, record })
+}
+
+/// Runs both parts of a day on `input` and compares them with the known answers.
+pub fn verify_day(day: u32, path: &Path, input: &str, known: &KnownAnswers) -> Vec<Check> {
+    let hash = hash_input(input);
+    let parts = [Part::One, Part::Two];
+    let answers = match registry::get(day) {
+        Some(solution) => solution.solve(input, &parts),
+        None => Err(AOCError::invalid_argument(format!("Day {day} is not implemented")))
+    };
+    let answers: Vec<Result<String, String>> = match answers {
+        Ok(answers) => answers.into_iter()
+            .map(|answer| answer.map_err(|error| error.to_string()))
+            .collect(),
+        Err(error) => vec![Err(error.in_file(path).to_string()); 2]
+    };
+
+    parts.into_iter()
+        .zip(answers)
+        .map(|(part, answer)| {
+            let status = match (&answer, known.get(day, &hash, part)) {
+                (Err(error), _) => Status::Failed(error.clone()),
+                (Ok(answer), Some(expected)) if answer == expected => Status::Match,
+                (Ok(_), Some(expected)) => Status::Mismatch { expected: expected.to_owned() },
+                (Ok(_), None) => Status::Missing
+            };
+            Check { part, answer: answer.ok(), status }
+        })
+        .collect()
+}
+
+/// Verifies every selected day and prints a line per part. Returns whether
+/// nothing regressed.
+pub fn verify(options: &VerifyOptions) -> Result<bool, AOCError> {
+    let mut known = KnownAnswers::load(&options.answers)?;
+    let mut checks: Vec<Check> = Vec::new();
+    let mut recorded = 0;
+
+    for &day in &options.days {
+        let path = match find_input(day) {
+            Ok(path) => path,
+            Err(_) => {
+                println!("Day {day:>2}         no input, skipped");
+                continue;
+            }
+        };
+        let input = read_input(&path)?;
+        for check in verify_day(day, &path, &input, &known) {
+            let answer = check.answer.as_deref().unwrap_or("-");
+            let status = match &check.status {
+                Status::Match => String::from("ok"),
+                Status::Mismatch { expected } => format!("MISMATCH, expected {expected}"),
+                Status::Missing if options.record => String::from("recorded"),
+                Status::Missing => String::from("unknown"),
+                Status::Failed(error) => format!("FAILED: {error}")
+            };
+            println!("Day {day:>2} part {}  {answer:<16} {status}", check.part.number());
+            checks.push(check);
+        }
+        if options.record {
+            for check in &checks[checks.len() - 2..] {
+                if let (Status::Missing, Some(answer)) = (&check.status, &check.answer) {
+                    known.insert(day, &hash_input(&input), &path, check.part, answer);
+                    recorded += 1;
+                }
+            }
+        }
+    }
+
+    let count = |wanted: fn(&Status) -> bool| checks.iter().filter(|check| wanted(&check.status)).count();
+    let regressions = count(|status| matches!(status, Status::Mismatch { .. } | Status::Failed(_)));
+    println!();
+    println!(
+        "{} matched, {} mismatched, {} failed, {} unknown",
+        count(|status| *status == Status::Match),
+        count(|status| matches!(status, Status::Mismatch { .. })),
+        count(|status| matches!(status, Status::Failed(_))),
+        count(|status| *status == Status::Missing)
+    );
+
+    if recorded > 0 {
+        write_atomically(&options.answers, &known.to_toml())?;
+        println!("Recorded {recorded} answers in {}", options.answers.display());
+    }
+    Ok(regressions == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "# Known answers\n\n[day5.0123456789abcdef]\ninput = \"day5/input.txt\"\npart1 = \"35\"\npart2 = \"46\"\n";
+
+    #[test]
+    fn test_known_answers() {
+        let known = KnownAnswers::parse(ANSWERS).unwrap();
+        assert_eq!(known.get(5, "0123456789abcdef", Part::Two), Some("46"));
+        assert_eq!(known.get(5, "fedcba9876543210", Part::Two), None);
+        assert_eq!(KnownAnswers::parse(&known.to_toml()).unwrap(), known);
+
+        let mut known = known;
+        known.insert(3, "0000000000000001", Path::new("day3/input.txt"), Part::One, "4361");
+        assert!(known.to_toml().contains("[day3.0000000000000001]\ninput = \"day3/input.txt\"\npart1 = \"4361\"\n"));
+        known.insert(3, "0000000000000001", Path::new("day3/input.txt"), Part::Two, "#1 \"quoted\"");
+        assert_eq!(KnownAnswers::parse(&known.to_toml()).unwrap().get(3, "0000000000000001", Part::Two), Some("#1 \"quoted\""));
+    }
+
+    #[test]
+    fn test_malformed_answers() {
+        let message = |contents: &str| KnownAnswers::parse(contents).unwrap_err().to_string();
+        assert!(message("part1 = \"35\"").contains("section first"));
+        assert!(message("[five.abc]").contains("Expected a section"));
+        assert!(message("[day5.abc]\npart1 = 35").contains("Expected a quoted value."));
+        let error = message("[day5.abc]\npart3 = \"1\"");
+        assert!(error.contains("Unknown key 'part3'"));
+        assert!(error.contains(":2:1"));
+    }
+
+    #[test]
+    fn test_verify_day() {
+        let path = Path::new("../day5/tests/input.txt");
+        let input = read_input(path).unwrap();
+        let hash = hash_input(&input);
+        let known = KnownAnswers::parse(&format!("[day5.{hash}]\npart1 = \"35\"\npart2 = \"47\"\n")).unwrap();
+
+        let checks = verify_day(5, path, &input, &known);
+        assert_eq!(checks[0].status, Status::Match);
+        assert_eq!(checks[1].status, Status::Mismatch { expected: String::from("47") });
+        assert_eq!(checks[1].answer.as_deref(), Some("46"));
+
+        let checks = verify_day(5, path, "seeds: 1\n", &known);
+        assert_eq!(checks[0].status, Status::Missing);
+        assert!(matches!(&checks[1].status, Status::Failed(error) if error.contains("Expected pairs")));
+    }
+
+    #[test]
+    fn test_hash_input() {
+        assert_eq!(hash_input(""), "cbf29ce484222325");
+        assert_eq!(hash_input("a"), "af63dc4c8601ec8c");
+        assert_ne!(hash_input("seeds: 1 2"), hash_input("seeds: 1 3"));
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, SystemTime};
+
+use aoc::args::parse_parts;
+use aoc::errors::AOCError;
+use aoc::{hash_input, read_input, Part};
+
+use crate::fetch::find_input;
+use crate::run::{local_input, parse_day};
+use crate::verify::{default_answers, KnownAnswers};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct WatchOptions {
+    pub day: u32,
+    pub parts: Vec<Part>,
+    pub input: Option<PathBuf>,
+    pub answers: PathBuf,
+    /// Passed on to the day after its input.
+    pub extra: Vec<String>,
+}
+
+/// When every watched file was last modified, `None` for files that don't
+/// exist (yet).
+pub type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+pub fn parse_args(args: &[String]) -> Result<WatchOptions, AOCError> {
+    let mut day = None;
+    let mut options = WatchOptions {
+        day: 0,
+        parts: vec![Part::One, Part::Two],
+        input: None,
+        answers: default_answers(),
+        extra: Vec::new()
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next()
+            .ok_or(AOCError::invalid_argument(format!("{name} needs a value")));
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(value("--day")?)?),
+            "--part" => options.parts = parse_parts(value("--part")?)?,
+            "--input" => options.input = Some(PathBuf::from(value("--input")?)),
+            "--answers" => options.answers = PathBuf::from(value("--answers")?),
+            "--" => options.extra.extend(args.by_ref().cloned()),
+            other => return Err(AOCError::invalid_argument(format!("Unknown option '{other}'")))
+        }
+    }
+
+    options.day = day.ok_or(AOCError::invalid_argument("--day is required"))?;
+    Ok(options)
+}
+
+/// The files under `paths`, which may be files or directories, with their
+/// modification times.
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut pending = paths.to_vec();
+    while let Some(path) = pending.pop() {
+        match fs::read_dir(&path) {
+            Ok(entries) => pending.extend(entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))),
+            Err(_) => {
+                let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+                snapshot.insert(path, modified);
+            }
+        }
+    }
+    snapshot
+}
+
+/// The files that were added, removed or modified between two snapshots.
+pub fn changes<'a>(before: &'a Snapshot, after: &'a Snapshot) -> Vec<&'a Path> {
+    let mut changed: Vec<&Path> = after.iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.as_path())
+        .collect();
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)).map(PathBuf::as_path));
+    changed.sort();
+    changed
+}
+
+/// Describes a new answer compared with the one from the previous run and
+/// the known answer for the input.
+pub fn describe(answer: Option<&str>, previous: Option<&Option<String>>, known: Option<&str>) -> String {
+    let Some(answer) = answer else {
+        return String::from("failed");
+    };
+    let mut description = String::from(answer);
+    match previous {
+        Some(Some(previous)) if previous == answer => description += " (unchanged)",
+        Some(Some(previous)) => description += &format!(" (was {previous})"),
+        Some(None) => description += " (failed before)",
+        None => ()
+    }
+    match known {
+        Some(known) if known == answer => description += ", matches the known answer",
+        Some(known) => description += &format!(", MISMATCH, the known answer is {known}"),
+        None => ()
+    }
+    description
+}
+
+/// Rebuilds the day and solves the parts on `input`, one answer per part or
+/// `None` for the parts that didn't get an answer. The build and the day
+/// report their errors on stderr themselves.
+fn build_and_run(options: &WatchOptions, input: &Path) -> Result<Vec<Option<String>>, AOCError> {
+    let part = match options.parts[..] {
+        [Part::One] => "1",
+        [Part::Two] => "2",
+        _ => "both"
+    };
+    let output = Command::new(std::env::var_os("CARGO").unwrap_or("cargo".into()))
+        .args(["run", "--quiet", "-p", &format!("day{}", options.day), "--", "--part", part])
+        .arg(input)
+        .args(&options.extra)
+        .stderr(Stdio::inherit())
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut answers = stdout.lines().map(|line| Some(line.to_owned()));
+    Ok(options.parts.iter().map(|_| answers.next().flatten()).collect())
+}
+
+/// Solves the day, then again whenever its sources or its input change,
+/// until Ctrl-C is pressed.
+pub fn watch(root: &Path, options: &WatchOptions) -> Result<(), AOCError> {
+    let (stop, stopped) = mpsc::channel();
+    ctrlc::set_handler(move || {
+        let _ = stop.send(());
+    }).map_err(io::Error::other)?;
+
+    let day = options.day;
+    let sources = root.join(format!("day{day}"));
+    if !sources.is_dir() {
+        return Err(AOCError::invalid_argument(format!("{} doesn't exist", sources.display())));
+    }
+    println!("Watching {} for changes, press Ctrl-C to stop", sources.display());
+
+    let mut before: Option<Snapshot> = None;
+    let mut previous: Vec<Option<String>> = Vec::new();
+    loop {
+        let input = match &options.input {
+            Some(input) => input.clone(),
+            None => find_input(day).unwrap_or(local_input(day))
+        };
+        let after = snapshot(&[sources.join("src"), sources.join("Cargo.toml"), input.clone()]);
+        if before.as_ref() != Some(&after) {
+            println!();
+            match &before {
+                Some(before) => {
+                    let changed: Vec<String> = changes(before, &after).iter().map(|path| path.display().to_string()).collect();
+                    println!("Changed: {}", changed.join(", "));
+                },
+                None => println!("Solving day {day} on {}", input.display())
+            }
+            before = Some(after);
+
+            match read_input(&input) {
+                Ok(text) => {
+                    let known = KnownAnswers::load(&options.answers).unwrap_or_else(|error| {
+                        eprintln!("{error}");
+                        KnownAnswers::default()
+                    });
+                    let answers = build_and_run(options, &input)?;
+                    for (i, (part, answer)) in options.parts.iter().zip(&answers).enumerate() {
+                        let known = known.get(day, &hash_input(&text), *part);
+                        println!("Part {}: {}", part.number(), describe(answer.as_deref(), previous.get(i), known));
+                    }
+                    previous = answers;
+                },
+                Err(error) => eprintln!("{error}")
+            }
+        }
+
+        match stopped.recv_timeout(POLL_INTERVAL) {
+            Err(RecvTimeoutError::Timeout) => (),
+            _ => break
+        }
+    }
+    println!();
+    println!("Stopped watching day {day}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot_changes() {
+        let directory = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(directory.join("src")).unwrap();
+        fs::write(directory.join("src/lib.rs"), "fn a() {}").unwrap();
+        let input = directory.join("input.txt");
+
+        let paths = [directory.join("src"), input.clone()];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 2);
+        assert_eq!(before.get(&input), Some(&None));
+
+        fs::write(&input, "1 2 3").unwrap();
+        fs::write(directory.join("src/main.rs"), "fn main() {}").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!(changes(&before, &after), vec![input.as_path(), directory.join("src/main.rs").as_path()]);
+
+        fs::remove_file(directory.join("src/lib.rs")).unwrap();
+        assert_eq!(changes(&after, &snapshot(&paths)), vec![directory.join("src/lib.rs").as_path()]);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(Some("35"), None, None), "35");
+        assert_eq!(describe(Some("35"), Some(&Some(String::from("35"))), Some("35")), "35 (unchanged), matches the known answer");
+        assert_eq!(describe(Some("36"), Some(&Some(String::from("35"))), Some("35")), "36 (was 35), MISMATCH, the known answer is 35");
+        assert_eq!(describe(Some("35"), Some(&None), None), "35 (failed before)");
+        assert_eq!(describe(None, Some(&Some(String::from("35"))), Some("35")), "failed");
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let options = parse_args(&args("--day 2 --part 1 --input day2/tests/input.txt -- 12 13 14")).unwrap();
+        assert_eq!((options.day, options.parts), (2, vec![Part::One]));
+        assert_eq!(options.input, Some(PathBuf::from("day2/tests/input.txt")));
+        assert_eq!(options.extra, vec!["12", "13", "14"]);
+        assert!(parse_args(&args("--part 2")).is_err());
+        assert!(parse_args(&args("--day 2 --verbose")).is_err());
+    }
+}